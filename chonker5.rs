@@ -25,6 +25,8 @@
 //! tracing-subscriber = { version = "0.3", features = ["env-filter"] }
 //! serde = { version = "1.0", features = ["derive"] }
 //! serde_json = "1.0"
+//! chrono = "0.4"
+//! tempfile = "3"
 //! ```
 
 use anyhow::Result;
@@ -33,23 +35,98 @@ use egui::{Align2, Color32, FontId, Rect, Response, RichText, Rounding, Sense, S
 use image::{ImageBuffer, Rgb, RgbImage};
 use pdfium_render::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::mpsc;
 
-// Teal and chrome color scheme
-const TERM_BG: Color32 = Color32::from_rgb(10, 15, 20);
-const TERM_FG: Color32 = Color32::from_rgb(26, 188, 156);
-const TERM_HIGHLIGHT: Color32 = Color32::from_rgb(22, 160, 133);
-const TERM_ERROR: Color32 = Color32::from_rgb(255, 80, 80);
-const TERM_DIM: Color32 = Color32::from_rgb(80, 100, 100);
-const TERM_YELLOW: Color32 = Color32::from_rgb(255, 200, 0);
-const TERM_GREEN: Color32 = Color32::from_rgb(46, 204, 113);
-const TERM_BLUE: Color32 = Color32::from_rgb(52, 152, 219);
-const CHROME: Color32 = Color32::from_rgb(82, 86, 89);
+// Shared with chonker5-tui; rust-script builds this file in place, so the path resolves
+#[allow(dead_code)]
+#[path = "src/export.rs"]
+mod export;
+#[path = "src/recent.rs"]
+mod recent;
+
+use recent::RecentFiles;
+
+// Teal and chrome color scheme, or black and white for high contrast
+#[derive(Clone, Copy)]
+struct Palette {
+    bg: Color32,
+    fg: Color32,
+    highlight: Color32,
+    error: Color32,
+    dim: Color32,
+    yellow: Color32,
+    green: Color32,
+    blue: Color32,
+    chrome: Color32,
+}
+
+const TERMINAL_PALETTE: Palette = Palette {
+    bg: Color32::from_rgb(10, 15, 20),
+    fg: Color32::from_rgb(26, 188, 156),
+    highlight: Color32::from_rgb(22, 160, 133),
+    error: Color32::from_rgb(255, 80, 80),
+    dim: Color32::from_rgb(80, 100, 100),
+    yellow: Color32::from_rgb(255, 200, 0),
+    green: Color32::from_rgb(46, 204, 113),
+    blue: Color32::from_rgb(52, 152, 219),
+    chrome: Color32::from_rgb(82, 86, 89),
+};
+
+// Every pair here clears WCAG AAA (7:1) against the black background
+const HIGH_CONTRAST_PALETTE: Palette = Palette {
+    bg: Color32::BLACK,
+    fg: Color32::WHITE,
+    highlight: Color32::from_rgb(0, 255, 255),
+    error: Color32::from_rgb(255, 140, 140),
+    dim: Color32::from_rgb(210, 210, 210),
+    yellow: Color32::from_rgb(255, 235, 0),
+    green: Color32::from_rgb(80, 255, 140),
+    blue: Color32::from_rgb(140, 200, 255),
+    chrome: Color32::from_rgb(230, 230, 230),
+};
+
+// Set from the app's settings at the start of each frame
+static HIGH_CONTRAST: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+fn palette() -> Palette {
+    if HIGH_CONTRAST.load(std::sync::atomic::Ordering::Relaxed) {
+        HIGH_CONTRAST_PALETTE
+    } else {
+        TERMINAL_PALETTE
+    }
+}
+
+/// Give an icon-style button a spoken name for screen readers.
+fn accessible(response: Response, label: &str) -> Response {
+    response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, label));
+    response
+}
+
+/// Like `accessible`, for buttons that toggle a mode on and off.
+fn accessible_toggle(response: Response, label: &str, on: bool) -> Response {
+    response.widget_info(|| egui::WidgetInfo::selected(egui::WidgetType::Checkbox, on, label));
+    response
+}
+
+// Font family the matrix views draw with; see Chonker5App::install_matrix_font
+const MATRIX_FONT_FAMILY: &str = "matrix";
+
+// Continuous page view
+const PAGE_GAP: f32 = 12.0;
+const FERRULES_TEST_BINARY: &str = "./target/release/test_ferrules_integration";
+const PAGE_PRELOAD: usize = 4; // Rendered pages kept beyond the visible range
+const PAGE_SETTLE: std::time::Duration = std::time::Duration::from_millis(400);
+/// How long a note comment or table cell must sit unchanged before it is written out
+const EDIT_SAVE_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
+// UI scale setting bounds
+const UI_SCALE_MIN: f32 = 0.75;
+const UI_SCALE_MAX: f32 = 2.5;
 
 // ============= MATRIX SELECTION =============
 #[derive(Clone, Debug)]
@@ -125,6 +202,12 @@ pub struct MatrixGrid {
     pub is_dragging_selection: bool, // Track if we're dragging a selection
     pub drag_start_pos: Option<(usize, usize)>, // Where the drag started
     pub drag_content: Vec<Vec<char>>, // Content being dragged
+    pub highlights: Vec<(usize, usize, usize)>, // Search matches as (row, col, len)
+    pub current_highlight: Option<usize>,
+    pub scroll_to: Option<(usize, usize)>,
+    pub font_size: f32,
+    pub cell_tints: Vec<Vec<Option<Color32>>>, // Per-cell background, e.g. the confidence heatmap
+    pub has_focus: bool,                        // Only the focused pane takes keyboard input
 }
 
 impl MatrixGrid {
@@ -152,12 +235,27 @@ impl MatrixGrid {
             is_dragging_selection: false,
             drag_start_pos: None,
             drag_content: Vec::new(),
+            highlights: Vec::new(),
+            current_highlight: None,
+            scroll_to: None,
+            font_size: 9.0,
+            cell_tints: Vec::new(),
+            has_focus: true,
         }
     }
 
     pub fn show(&mut self, ui: &mut egui::Ui) -> Response {
-        const TERM_TEAL: Color32 = Color32::from_rgb(26, 188, 156);
-        const TERM_TEAL_FADED: Color32 = Color32::from_rgba_premultiplied(26, 188, 156, 80);
+        let text_color = palette().fg;
+        let selection_fill =
+            Color32::from_rgba_premultiplied(text_color.r(), text_color.g(), text_color.b(), 80);
+
+        // Cells follow the font's own metrics so columns line up at any size or DPI
+        let font_id = FontId::new(
+            self.font_size,
+            egui::FontFamily::Name(MATRIX_FONT_FAMILY.into()),
+        );
+        self.char_size =
+            ui.fonts(|f| Vec2::new(f.glyph_width(&font_id, 'M'), f.row_height(&font_id)));
 
         let (response, painter) = ui.allocate_painter(
             Vec2::new(
@@ -168,7 +266,22 @@ impl MatrixGrid {
         );
 
         let rect = response.rect;
-        let font_id = egui::FontId::monospace(9.0);
+        // Screen readers get the size and the line under the cursor
+        response.widget_info(|| {
+            let mut info = egui::WidgetInfo::labeled(
+                egui::WidgetType::TextEdit,
+                format!(
+                    "Character matrix, {} rows by {} columns",
+                    self.matrix.len(),
+                    self.matrix.first().map_or(0, Vec::len)
+                ),
+            );
+            info.current_text_value = self
+                .cursor_pos
+                .and_then(|(row, _)| self.matrix.get(row))
+                .map(|line| line.iter().collect::<String>().trim_end().to_string());
+            info
+        });
 
         // Update cursor blink
         let now = Instant::now();
@@ -310,7 +423,47 @@ impl MatrixGrid {
         }
 
         // Draw background
-        painter.rect_filled(rect, 0.0, TERM_BG);
+        painter.rect_filled(rect, 0.0, palette().bg);
+
+        for (row_idx, row) in self.cell_tints.iter().enumerate() {
+            for (col_idx, tint) in row.iter().enumerate() {
+                if let Some(color) = tint {
+                    let cell_rect = Rect::from_min_size(
+                        rect.min
+                            + Vec2::new(
+                                col_idx as f32 * self.char_size.x,
+                                row_idx as f32 * self.char_size.y,
+                            ),
+                        self.char_size,
+                    );
+                    painter.rect_filled(cell_rect, 0.0, *color);
+                }
+            }
+        }
+
+        // Draw search highlights under the characters
+        for (i, &(row, col, len)) in self.highlights.iter().enumerate() {
+            let highlight_rect = Rect::from_min_size(
+                rect.min + Vec2::new(col as f32 * self.char_size.x, row as f32 * self.char_size.y),
+                Vec2::new(len as f32 * self.char_size.x, self.char_size.y),
+            );
+            let color = if self.current_highlight == Some(i) {
+                palette().yellow.gamma_multiply(0.7)
+            } else {
+                palette().yellow.gamma_multiply(0.25)
+            };
+            painter.rect_filled(highlight_rect, 1.0, color);
+        }
+        if let Some((row, col)) = self.scroll_to.take() {
+            ui.scroll_to_rect(
+                Rect::from_min_size(
+                    rect.min
+                        + Vec2::new(col as f32 * self.char_size.x, row as f32 * self.char_size.y),
+                    self.char_size,
+                ),
+                Some(egui::Align::Center),
+            );
+        }
 
         // Draw matrix with selection
         for (row_idx, row) in self.matrix.iter().enumerate() {
@@ -327,7 +480,7 @@ impl MatrixGrid {
                         pos - Vec2::new(0.0, self.char_size.y * 0.1),
                         Vec2::new(self.char_size.x, self.char_size.y * 1.2),
                     );
-                    painter.rect_filled(selection_rect, 2.0, TERM_TEAL_FADED);
+                    painter.rect_filled(selection_rect, 2.0, selection_fill);
                 }
 
                 // Draw character
@@ -336,7 +489,7 @@ impl MatrixGrid {
                 } else if ch == '·' {
                     Color32::from_gray(80)
                 } else {
-                    TERM_FG
+                    palette().fg
                 };
 
                 painter.text(
@@ -364,7 +517,7 @@ impl MatrixGrid {
                         Vec2::new(self.char_size.x * 0.8, self.char_size.y * 1.2),
                     ),
                     0.0,
-                    TERM_TEAL,
+                    text_color,
                 );
 
                 if cursor_col < self.matrix[cursor_row].len() {
@@ -374,7 +527,7 @@ impl MatrixGrid {
                         egui::Align2::CENTER_CENTER,
                         ch.to_string(),
                         font_id.clone(),
-                        TERM_BG,
+                        palette().bg,
                     );
                 }
             }
@@ -429,8 +582,16 @@ impl MatrixGrid {
             }
         }
 
+        let keyboard = self.has_focus && !ui.ctx().wants_keyboard_input();
+        if keyboard && !self.matrix.is_empty() {
+            self.handle_navigation_keys(ui, rect);
+        }
+
         // Handle cut/copy/paste operations
         ui.input(|i| {
+            if !keyboard {
+                return;
+            }
             if i.modifiers.command || i.modifiers.ctrl {
                 // Copy (Ctrl+C)
                 if i.key_pressed(egui::Key::C) {
@@ -462,9 +623,14 @@ impl MatrixGrid {
                                 }
                             }
 
-                            // For small selections, also copy as text to system clipboard
+                            // For small selections, also copy as text to system clipboard.
+                            // Blocks with several columns go as TSV so spreadsheets split
+                            // them into cells; Ctrl+Shift+C keeps the plain layout.
                             if selection_size < 10000 {
-                                let selected_text = self.selection.get_selected_text(&self.matrix);
+                                let selected_text = match selection_to_tsv(&self.clipboard) {
+                                    Some(tsv) if !i.modifiers.shift => tsv,
+                                    _ => self.selection.get_selected_text(&self.matrix),
+                                };
                                 if !selected_text.is_empty()
                                     && selected_text != "[Selection too large]"
                                 {
@@ -585,6 +751,468 @@ impl MatrixGrid {
 
         response
     }
+
+    /// Cursor movement, selection and deletion keys, mirroring the TUI's matrix pane.
+    fn handle_navigation_keys(&mut self, ui: &egui::Ui, rect: Rect) {
+        let (keys, shift, command) = ui.input(|i| {
+            let keys: Vec<egui::Key> = i
+                .events
+                .iter()
+                .filter_map(|event| match event {
+                    egui::Event::Key {
+                        key, pressed: true, ..
+                    } => Some(*key),
+                    _ => None,
+                })
+                .collect();
+            (keys, i.modifiers.shift, i.modifiers.command || i.modifiers.ctrl)
+        });
+
+        let last_row = self.matrix.len() - 1;
+        for key in keys {
+            // While selecting, the moving end of the selection acts as the cursor
+            let (row, col) = self.selection.end.or(self.cursor_pos).unwrap_or((0, 0));
+            let target = match key {
+                egui::Key::ArrowUp => Some((row.saturating_sub(1), col)),
+                egui::Key::ArrowDown => Some((row + 1, col)),
+                egui::Key::ArrowLeft => Some((row, col.saturating_sub(1))),
+                egui::Key::ArrowRight => Some((row, col + 1)),
+                egui::Key::Home if command => Some((0, col)),
+                egui::Key::End if command => Some((last_row, col)),
+                egui::Key::Home => Some((row, 0)),
+                egui::Key::End => Some((row, usize::MAX)),
+                egui::Key::Enter => Some((row + 1, 0)),
+                egui::Key::A if command => {
+                    let width = self.matrix.iter().map(|r| r.len()).max().unwrap_or(0);
+                    self.selection.start = Some((0, 0));
+                    self.selection.end = Some((last_row, width.saturating_sub(1)));
+                    self.cursor_pos = None;
+                    None
+                }
+                egui::Key::Escape => {
+                    self.selection.start = None;
+                    self.selection.end = None;
+                    None
+                }
+                egui::Key::Backspace | egui::Key::Delete => {
+                    if let (Some(start), Some(end)) = (self.selection.start, self.selection.end) {
+                        for r in start.0.min(end.0)..=start.0.max(end.0).min(last_row) {
+                            let row = &mut self.matrix[r];
+                            for c in start.1.min(end.1)..=start.1.max(end.1) {
+                                if let Some(cell) = row.get_mut(c) {
+                                    *cell = ' ';
+                                }
+                            }
+                        }
+                        self.modified = true;
+                        None
+                    } else if let Some((row, col)) = self.cursor_pos {
+                        let col = if key == egui::Key::Backspace {
+                            if col == 0 {
+                                continue;
+                            }
+                            col - 1
+                        } else {
+                            col
+                        };
+                        if let Some(cell) = self.matrix[row].get_mut(col) {
+                            *cell = ' ';
+                            self.modified = true;
+                        }
+                        Some((row, col))
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            };
+
+            let Some((new_row, new_col)) = target else {
+                continue;
+            };
+            let new_row = new_row.min(last_row);
+            let new_col = new_col.min(self.matrix[new_row].len().saturating_sub(1));
+
+            if shift && key != egui::Key::Enter {
+                if self.selection.start.is_none() {
+                    self.selection.start = Some((row, col));
+                }
+                self.selection.end = Some((new_row, new_col));
+                self.cursor_pos = None;
+            } else {
+                self.selection.start = None;
+                self.selection.end = None;
+                self.cursor_pos = Some((new_row, new_col));
+            }
+            self.cursor_visible = true;
+            self.last_blink = Instant::now();
+            ui.scroll_to_rect(
+                Rect::from_min_size(
+                    rect.min
+                        + Vec2::new(
+                            new_col as f32 * self.char_size.x,
+                            new_row as f32 * self.char_size.y,
+                        ),
+                    self.char_size,
+                ),
+                None,
+            );
+        }
+    }
+}
+
+/// Cells that differ between two matrices, sized to cover both. Missing cells
+/// count as blank, so a shorter row only differs where the longer has text.
+fn cell_diff(left: &[Vec<char>], right: &[Vec<char>]) -> Vec<Vec<bool>> {
+    let rows = left.len().max(right.len());
+    let cols = left.iter().chain(right).map(Vec::len).max().unwrap_or(0);
+    let at = |matrix: &[Vec<char>], row: usize, col: usize| {
+        matrix.get(row).and_then(|r| r.get(col)).copied().unwrap_or(' ')
+    };
+    (0..rows)
+        .map(|row| (0..cols).map(|col| at(left, row, col) != at(right, row, col)).collect())
+        .collect()
+}
+
+/// Tab-separate a copied block for spreadsheets. Columns split at gutters of
+/// two or more character columns that are blank in every row; None if the
+/// block has only one column.
+fn selection_to_tsv(rows: &[Vec<char>]) -> Option<String> {
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let blank =
+        |col: usize| rows.iter().all(|row| row.get(col).is_none_or(|c| c.is_whitespace()));
+
+    let mut spans = Vec::new();
+    let mut start = None;
+    let mut gap = 0;
+    for col in 0..width {
+        if !blank(col) {
+            start.get_or_insert(col);
+            gap = 0;
+            continue;
+        }
+        gap += 1;
+        if gap == 2 {
+            if let Some(start) = start.take() {
+                spans.push(start..col - 1);
+            }
+        }
+    }
+    if let Some(start) = start {
+        spans.push(start..width);
+    }
+    if spans.len() < 2 {
+        return None;
+    }
+
+    let lines: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            spans
+                .iter()
+                .map(|span| {
+                    let cell = &row[span.start.min(row.len())..span.end.min(row.len())];
+                    cell.iter().collect::<String>().trim().to_string()
+                })
+                .collect::<Vec<_>>()
+                .join("\t")
+        })
+        .collect();
+    Some(lines.join("\n"))
+}
+
+/// How sure the engine is that a character landed in the right cell, from 0
+/// to 1. `grid_x`/`grid_y` are its position in cells before snapping. The
+/// score drops the further it had to be snapped, when it shares its cell
+/// with other characters, and when the glyph has no real Unicode mapping.
+fn placement_confidence(ch: char, grid_x: f32, grid_y: f32, sharing: usize) -> f32 {
+    let snap = (grid_x - grid_x.round()).abs() + (grid_y - grid_y.round()).abs();
+    let mut confidence = (1.0 - snap).max(0.0);
+    if ch == '\u{fffd}' || ch.is_control() || ('\u{e000}'..='\u{f8ff}').contains(&ch) {
+        confidence *= 0.25;
+    }
+    confidence / sharing.max(1) as f32
+}
+
+/// Translucent red-to-green ramp for the confidence heatmap.
+fn heat_color(confidence: f32) -> Color32 {
+    let c = confidence.clamp(0.0, 1.0);
+    let (r, g) = if c < 0.5 {
+        (255.0, 510.0 * c)
+    } else {
+        (510.0 * (1.0 - c), 255.0)
+    };
+    Color32::from_rgba_unmultiplied(r as u8, g as u8, 60, 90)
+}
+
+// ============= PAGE RENDERING =============
+/// Bind to PDFium: a copy in ./lib first, as the TUI does, then the system library.
+fn bind_pdfium() -> Result<Pdfium> {
+    let bindings =
+        Pdfium::bind_to_library(Pdfium::pdfium_platform_library_name_at_path("./lib/"))
+            .or_else(|_| Pdfium::bind_to_system_library())
+            .or_else(|_| Pdfium::bind_to_library("/usr/local/lib/libpdfium.dylib"))
+            .map_err(|e| anyhow::anyhow!("Failed to bind pdfium: {}", e))?;
+    Ok(Pdfium::new(bindings))
+}
+
+/// Render one page (0-based) at `dpi`, in-process through PDFium. mutool is
+/// only tried when PDFium can't be loaded or can't open the file.
+fn render_page_image(pdf_path: &Path, page: usize, dpi: f32) -> Result<image::DynamicImage> {
+    render_page_with_pdfium(pdf_path, page, dpi).or_else(|pdfium_err| {
+        tracing::warn!("PDFium render failed ({}), trying mutool", pdfium_err);
+        render_page_with_mutool(pdf_path, page, dpi)
+            .map_err(|e| anyhow::anyhow!("{} (PDFium: {})", e, pdfium_err))
+    })
+}
+
+fn render_page_with_pdfium(pdf_path: &Path, page: usize, dpi: f32) -> Result<image::DynamicImage> {
+    let pdfium = bind_pdfium()?;
+    let document = pdfium.load_pdf_from_file(pdf_path, None)?;
+    let page = document.pages().get(page as u16)?;
+    let config =
+        PdfRenderConfig::new().set_target_width((page.width().value * dpi / 72.0) as i32);
+    let bitmap = page.render_with_config(&config)?;
+    let image = image::RgbaImage::from_raw(
+        bitmap.width() as u32,
+        bitmap.height() as u32,
+        bitmap.as_rgba_bytes().to_vec(),
+    )
+    .ok_or_else(|| anyhow::anyhow!("PDFium returned a malformed bitmap"))?;
+    Ok(image::DynamicImage::ImageRgba8(image))
+}
+
+fn render_page_with_mutool(pdf_path: &Path, page: usize, dpi: f32) -> Result<image::DynamicImage> {
+    // A fresh file per render, so tabs or app instances on the same page don't collide
+    let temp_png = tempfile::Builder::new()
+        .prefix("chonker5_page_")
+        .suffix(".png")
+        .tempfile()?;
+    let output = Command::new("mutool")
+        .arg("draw")
+        .arg("-o")
+        .arg(temp_png.path())
+        .arg("-r")
+        .arg(dpi.to_string())
+        .arg("-F")
+        .arg("png")
+        .arg(pdf_path)
+        .arg((page + 1).to_string())
+        .output()
+        .map_err(|e| anyhow::anyhow!("Failed to run mutool: {}", e))?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "mutool: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    // The temp file is removed when `temp_png` drops
+    Ok(image::open(temp_png.path())?)
+}
+
+// ============= DARK MODE =============
+// Pages are split into tiles; tiles dominated by mid-tones are taken to be
+// figures and keep their colours, while paper and ink get their lightness
+// inverted with the hue kept, so highlights and coloured text stay readable.
+const FIGURE_TILE: u32 = 24;
+const FIGURE_MIDTONE_RATIO: f32 = 0.35;
+
+fn smart_invert(image: &mut image::RgbaImage) {
+    let (width, height) = image.dimensions();
+    let cols = width.div_ceil(FIGURE_TILE);
+    let rows = height.div_ceil(FIGURE_TILE);
+
+    let mut figure = vec![false; (cols * rows) as usize];
+    for ty in 0..rows {
+        for tx in 0..cols {
+            let (x0, y0) = (tx * FIGURE_TILE, ty * FIGURE_TILE);
+            let (x1, y1) = ((x0 + FIGURE_TILE).min(width), (y0 + FIGURE_TILE).min(height));
+            let mut midtones = 0;
+            for y in y0..y1 {
+                for x in x0..x1 {
+                    if is_midtone(image.get_pixel(x, y)) {
+                        midtones += 1;
+                    }
+                }
+            }
+            let ratio = midtones as f32 / ((x1 - x0) * (y1 - y0)) as f32;
+            figure[(ty * cols + tx) as usize] = ratio > FIGURE_MIDTONE_RATIO;
+        }
+    }
+
+    // Fill notches along figure edges, e.g. a white patch of sky in a photo
+    let marked = figure.clone();
+    for ty in 0..rows {
+        for tx in 0..cols {
+            let neighbours = [
+                (tx > 0).then(|| (tx - 1, ty)),
+                (tx + 1 < cols).then_some((tx + 1, ty)),
+                (ty > 0).then(|| (tx, ty - 1)),
+                (ty + 1 < rows).then_some((tx, ty + 1)),
+            ];
+            let count = neighbours
+                .iter()
+                .flatten()
+                .filter(|(x, y)| marked[(y * cols + x) as usize])
+                .count();
+            if count >= 3 {
+                figure[(ty * cols + tx) as usize] = true;
+            }
+        }
+    }
+
+    for (x, y, pixel) in image.enumerate_pixels_mut() {
+        if !figure[((y / FIGURE_TILE) * cols + x / FIGURE_TILE) as usize] {
+            invert_lightness(pixel);
+        }
+    }
+}
+
+/// Neither paper nor ink: a saturated colour or a mid grey.
+fn is_midtone(pixel: &image::Rgba<u8>) -> bool {
+    let [r, g, b, _] = pixel.0;
+    let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+    let lightness = (max as u16 + min as u16) / 2;
+    max - min > 60 || (48..208).contains(&lightness)
+}
+
+/// Flip HSL lightness while keeping hue: shifting every channel by
+/// 255 - max - min maps max to 255 - min and min to 255 - max.
+fn invert_lightness(pixel: &mut image::Rgba<u8>) {
+    let [r, g, b, a] = pixel.0;
+    let shift = 255 - r.max(g).max(b) as i16 - r.min(g).min(b) as i16;
+    let channel = |c: u8| (c as i16 + shift) as u8;
+    *pixel = image::Rgba([channel(r), channel(g), channel(b), a]);
+}
+
+// ============= EXPORT =============
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ExportKind {
+    Document(export::ExportFormat),
+    TableCsv,
+    PngOverlay,
+}
+
+impl ExportKind {
+    const ALL: [ExportKind; 7] = [
+        ExportKind::Document(export::ExportFormat::Markdown),
+        ExportKind::Document(export::ExportFormat::Json),
+        ExportKind::Document(export::ExportFormat::Csv),
+        ExportKind::TableCsv,
+        ExportKind::Document(export::ExportFormat::Hocr),
+        ExportKind::PngOverlay,
+        ExportKind::Document(export::ExportFormat::Text),
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            ExportKind::Document(export::ExportFormat::Markdown) => "Markdown",
+            ExportKind::Document(export::ExportFormat::Json) => "JSON",
+            ExportKind::Document(export::ExportFormat::Csv) => "CSV (lines)",
+            ExportKind::Document(export::ExportFormat::Hocr) => "hOCR",
+            ExportKind::Document(export::ExportFormat::Text) => "Plain text",
+            ExportKind::TableCsv => "CSV (table regions)",
+            ExportKind::PngOverlay => "PNG with region overlay",
+        }
+    }
+}
+
+struct ExportDialog {
+    open: bool,
+    kind: ExportKind,
+    destination: Option<PathBuf>,
+    include_notes: bool,
+}
+
+struct ExportJob {
+    pdf_path: PathBuf,
+    kind: ExportKind,
+    pages: Vec<usize>,
+    destination: PathBuf,
+    /// The page on screen, with its edits, used instead of re-extracting it
+    edited_page: Option<(usize, CharacterMatrix)>,
+    /// Written alongside as `<stem>.notes.md` when set
+    notes: Option<Annotations>,
+    /// Limits a TableCsv export to the region with this `region_id`
+    only_region: Option<usize>,
+}
+
+/// Outline a rectangle in place, clipped to the image.
+fn draw_rect_outline(image: &mut RgbImage, rect: [u32; 4], color: Rgb<u8>, thickness: u32) {
+    let [x0, y0, x1, y1] = rect;
+    let (width, height) = image.dimensions();
+    for y in y0..y1.min(height) {
+        for x in x0..x1.min(width) {
+            let on_edge = x < x0 + thickness
+                || x + thickness >= x1
+                || y < y0 + thickness
+                || y + thickness >= y1;
+            if on_edge {
+                image.put_pixel(x, y, color);
+            }
+        }
+    }
+}
+
+// ============= SEARCH =============
+/// Non-overlapping matches of `query` within single matrix rows, as (row, col).
+pub fn find_matches(matrix: &[Vec<char>], query: &str, case_sensitive: bool) -> Vec<(usize, usize)> {
+    let needle: Vec<char> = query.chars().collect();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let same = |a: char, b: char| {
+        if case_sensitive {
+            a == b
+        } else {
+            a.to_lowercase().eq(b.to_lowercase())
+        }
+    };
+
+    let mut matches = Vec::new();
+    for (row_idx, row) in matrix.iter().enumerate() {
+        let mut col = 0;
+        while col + needle.len() <= row.len() {
+            if needle.iter().zip(&row[col..]).all(|(&n, &c)| same(n, c)) {
+                matches.push((row_idx, col));
+                col += needle.len();
+            } else {
+                col += 1;
+            }
+        }
+    }
+    matches
+}
+
+/// Overwrite `len` cells from `col` with the replacement. Shorter replacements
+/// are padded with spaces so the rest of the row keeps its position; longer
+/// ones run over the following cells up to the end of the row.
+pub fn replace_cells(row: &mut [char], col: usize, len: usize, replacement: &str) {
+    let width = len.max(replacement.chars().count());
+    let chars = replacement.chars().chain(std::iter::repeat(' ')).take(width);
+    for (cell, ch) in row.iter_mut().skip(col).zip(chars) {
+        *cell = ch;
+    }
+}
+
+#[derive(Default)]
+struct SearchState {
+    open: bool,
+    focus_query: bool,
+    query: String,
+    replacement: String,
+    case_sensitive: bool,
+    current: usize,
+    pending: Option<SearchAction>, // Queued from the keyboard (F3 / Shift+F3)
+}
+
+#[derive(Clone, Copy, Debug)]
+enum SearchAction {
+    Next,
+    Previous,
+    Replace,
+    ReplaceAll,
+    SelectRegion(usize),
 }
 
 // ============= CHARACTER MATRIX ENGINE =============
@@ -600,6 +1228,21 @@ pub struct CharacterMatrix {
 }
 
 impl CharacterMatrix {
+    /// Per-cell confidence taken from the regions covering each cell, using
+    /// the lowest where regions overlap. Cells outside every region are None.
+    pub fn confidence_map(&self) -> Vec<Vec<Option<f32>>> {
+        let mut map = vec![vec![None; self.width]; self.height];
+        for region in &self.text_regions {
+            for row in map.iter_mut().skip(region.bbox.y).take(region.bbox.height) {
+                for cell in row.iter_mut().skip(region.bbox.x).take(region.bbox.width) {
+                    let confidence = cell.map_or(region.confidence, |c: f32| c.min(region.confidence));
+                    *cell = Some(confidence);
+                }
+            }
+        }
+        map
+    }
+
     pub fn new(width: usize, height: usize) -> Self {
         let matrix = vec![vec![' '; width]; height];
         Self {
@@ -620,6 +1263,271 @@ pub struct TextRegion {
     pub confidence: f32,
     pub text_content: String,
     pub region_id: usize,
+    #[serde(default)]
+    pub block_type: BlockType,
+    /// Cell grid once a Table region has been opened in the table editor
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub table: Option<TableGrid>,
+}
+
+impl TextRegion {
+    pub fn to_extracted(&self) -> export::ExtractedRegion {
+        export::ExtractedRegion {
+            id: self.region_id + 1,
+            x: self.bbox.x,
+            y: self.bbox.y,
+            width: self.bbox.width,
+            height: self.bbox.height,
+            block_type: self.block_type.label().to_string(),
+            confidence: self.confidence,
+            text: self.text_content.clone(),
+            // Tables never opened in the editor still get a grid, so column checks see them
+            table: match (&self.table, self.block_type) {
+                (Some(table), _) => Some(table.to_extracted()),
+                (None, BlockType::Table) => {
+                    Some(TableGrid::from_text(&self.text_content).to_extracted())
+                }
+                (None, _) => None,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BlockType {
+    #[default]
+    Paragraph,
+    Heading,
+    Table,
+    List,
+    Figure,
+    Caption,
+    Header,
+    Footer,
+}
+
+impl BlockType {
+    pub const ALL: [BlockType; 8] = [
+        BlockType::Paragraph,
+        BlockType::Heading,
+        BlockType::Table,
+        BlockType::List,
+        BlockType::Figure,
+        BlockType::Caption,
+        BlockType::Header,
+        BlockType::Footer,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BlockType::Paragraph => "Paragraph",
+            BlockType::Heading => "Heading",
+            BlockType::Table => "Table",
+            BlockType::List => "List",
+            BlockType::Figure => "Figure",
+            BlockType::Caption => "Caption",
+            BlockType::Header => "Header",
+            BlockType::Footer => "Footer",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableGrid {
+    pub cells: Vec<Vec<String>>,
+    pub header_rows: usize,
+}
+
+impl TableGrid {
+    /// Split matrix text into cells: one row per non-blank line, with columns
+    /// separated by runs of two or more spaces.
+    pub fn from_text(text: &str) -> Self {
+        let mut cells: Vec<Vec<String>> = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                line.split("  ")
+                    .map(str::trim)
+                    .filter(|cell| !cell.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .collect();
+        let columns = cells.iter().map(Vec::len).max().unwrap_or(0).max(1);
+        for row in &mut cells {
+            row.resize(columns, String::new());
+        }
+        Self {
+            header_rows: usize::from(cells.len() > 1),
+            cells,
+        }
+    }
+
+    pub fn columns(&self) -> usize {
+        self.cells.first().map_or(0, Vec::len)
+    }
+
+    pub fn add_row(&mut self) {
+        self.cells.push(vec![String::new(); self.columns().max(1)]);
+    }
+
+    pub fn remove_row(&mut self, row: usize) {
+        if row < self.cells.len() {
+            self.cells.remove(row);
+            self.header_rows = self.header_rows.min(self.cells.len());
+        }
+    }
+
+    pub fn add_column(&mut self) {
+        for row in &mut self.cells {
+            row.push(String::new());
+        }
+    }
+
+    pub fn remove_column(&mut self, column: usize) {
+        for row in &mut self.cells {
+            if column < row.len() {
+                row.remove(column);
+            }
+        }
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut out = String::new();
+        for row in &self.cells {
+            let fields: Vec<String> = row
+                .iter()
+                .map(|cell| {
+                    if cell.contains([',', '"', '\n']) {
+                        format!("\"{}\"", cell.replace('"', "\"\""))
+                    } else {
+                        cell.clone()
+                    }
+                })
+                .collect();
+            out.push_str(&fields.join(","));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Markdown has exactly one header row, so extra header rows become body rows.
+    pub fn to_markdown(&self) -> String {
+        self.to_extracted().to_markdown()
+    }
+
+    pub fn to_extracted(&self) -> export::ExtractedTable {
+        export::ExtractedTable {
+            cells: self.cells.clone(),
+            header_rows: self.header_rows,
+        }
+    }
+}
+
+/// Hand-edited regions, saved next to the PDF so that re-extracting a page
+/// doesn't recompute the edits away.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RegionOverrides {
+    /// 1-based page number -> the page's regions as last edited
+    pages: BTreeMap<usize, Vec<TextRegion>>,
+}
+
+impl RegionOverrides {
+    fn path_for(pdf_path: &Path) -> PathBuf {
+        pdf_path.with_extension("regions.json")
+    }
+
+    fn load(pdf_path: &Path) -> Result<Self> {
+        let path = Self::path_for(pdf_path);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    fn save(&self, pdf_path: &Path) -> Result<()> {
+        std::fs::write(
+            Self::path_for(pdf_path),
+            serde_json::to_string_pretty(self)?,
+        )?;
+        Ok(())
+    }
+}
+
+/// A reviewer's highlight on a page with an optional comment. Like region
+/// boxes, positions are matrix cells.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Annotation {
+    bbox: CharBBox,
+    /// Matrix text under the highlight when it was drawn
+    quote: String,
+    #[serde(default)]
+    comment: String,
+    #[serde(default)]
+    author: String,
+    #[serde(default)]
+    created: String,
+}
+
+/// Annotations for a PDF, kept in `<pdf>.notes.json` next to it so they can
+/// be passed around with the document for review.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Annotations {
+    /// 1-based page number -> the page's annotations
+    pages: BTreeMap<usize, Vec<Annotation>>,
+}
+
+impl Annotations {
+    fn path_for(pdf_path: &Path) -> PathBuf {
+        pdf_path.with_extension("notes.json")
+    }
+
+    fn load(pdf_path: &Path) -> Result<Self> {
+        let path = Self::path_for(pdf_path);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    fn save(&self, pdf_path: &Path) -> Result<()> {
+        std::fs::write(
+            Self::path_for(pdf_path),
+            serde_json::to_string_pretty(self)?,
+        )?;
+        Ok(())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pages.values().all(Vec::is_empty)
+    }
+
+    /// Notes on the given 0-based pages as a Markdown list, empty if there are none.
+    fn to_markdown(&self, source: &str, pages: &[usize]) -> String {
+        let mut out = String::new();
+        for &page in pages {
+            let Some(notes) = self.pages.get(&(page + 1)).filter(|n| !n.is_empty()) else {
+                continue;
+            };
+            out.push_str(&format!("\n## Page {}\n\n", page + 1));
+            for note in notes {
+                let quote = note.quote.split_whitespace().collect::<Vec<_>>().join(" ");
+                out.push_str(&format!("- \"{}\"", quote));
+                if !note.comment.trim().is_empty() {
+                    out.push_str(&format!(" — {}", note.comment.trim().replace('\n', " ")));
+                }
+                if !note.author.is_empty() || !note.created.is_empty() {
+                    let byline = format!("{} {}", note.author, note.created);
+                    out.push_str(&format!(" ({})", byline.trim()));
+                }
+                out.push('\n');
+            }
+        }
+        if out.is_empty() {
+            return out;
+        }
+        format!("# Notes: {}\n{}", source, out)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -631,6 +1539,16 @@ pub struct CharBBox {
 }
 
 impl CharBBox {
+    /// Box spanning two opposite corner cells, both included.
+    pub fn from_corners(a: (usize, usize), b: (usize, usize)) -> Self {
+        Self {
+            x: a.0.min(b.0),
+            y: a.1.min(b.1),
+            width: a.0.abs_diff(b.0) + 1,
+            height: a.1.abs_diff(b.1) + 1,
+        }
+    }
+
     pub fn contains(&self, x: usize, y: usize) -> bool {
         x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
     }
@@ -638,6 +1556,24 @@ impl CharBBox {
     pub fn area(&self) -> usize {
         self.width * self.height
     }
+
+    /// Matrix text under the box, one line per row.
+    pub fn text_in(&self, matrix: &[Vec<char>]) -> String {
+        matrix
+            .iter()
+            .skip(self.y)
+            .take(self.height)
+            .map(|row| {
+                row.iter()
+                    .skip(self.x)
+                    .take(self.width)
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -677,12 +1613,7 @@ impl CharacterMatrixEngine {
     }
 
     pub fn find_optimal_character_dimensions(&self, pdf_path: &Path) -> Result<(f32, f32)> {
-        let pdfium = Pdfium::new(
-            Pdfium::bind_to_system_library()
-                .or_else(|_| Pdfium::bind_to_library("./lib/libpdfium.dylib"))
-                .or_else(|_| Pdfium::bind_to_library("/usr/local/lib/libpdfium.dylib"))
-                .map_err(|e| anyhow::anyhow!("Failed to bind pdfium: {}", e))?,
-        );
+        let pdfium = bind_pdfium()?;
 
         let document = pdfium.load_pdf_from_file(pdf_path, None)?;
         if document.pages().is_empty() {
@@ -718,12 +1649,7 @@ impl CharacterMatrixEngine {
         pdf_path: &PathBuf,
         target_page_index: usize,
     ) -> Result<Vec<PreciseTextObject>> {
-        let pdfium = Pdfium::new(
-            Pdfium::bind_to_system_library()
-                .or_else(|_| Pdfium::bind_to_library("./lib/libpdfium.dylib"))
-                .or_else(|_| Pdfium::bind_to_library("/usr/local/lib/libpdfium.dylib"))
-                .map_err(|e| anyhow::anyhow!("Failed to bind pdfium: {}", e))?,
-        );
+        let pdfium = bind_pdfium()?;
 
         let document = pdfium.load_pdf_from_file(pdf_path, None)?;
         let mut text_objects = Vec::new();
@@ -787,12 +1713,7 @@ impl CharacterMatrixEngine {
         &self,
         pdf_path: &PathBuf,
     ) -> Result<Vec<PreciseTextObject>> {
-        let pdfium = Pdfium::new(
-            Pdfium::bind_to_system_library()
-                .or_else(|_| Pdfium::bind_to_library("./lib/libpdfium.dylib"))
-                .or_else(|_| Pdfium::bind_to_library("/usr/local/lib/libpdfium.dylib"))
-                .map_err(|e| anyhow::anyhow!("Failed to bind pdfium: {}", e))?,
-        );
+        let pdfium = bind_pdfium()?;
 
         let document = pdfium.load_pdf_from_file(pdf_path, None)?;
         let mut text_objects = Vec::new();
@@ -938,6 +1859,7 @@ impl CharacterMatrixEngine {
                             current.bbox.x = new_x;
                             current.bbox.width = new_end - new_x;
                             current.text_content.push_str(&other.text_content);
+                            current.confidence = current.confidence.min(other.confidence);
                             processed[j] = true;
                             merged_any = true;
                         }
@@ -987,9 +1909,25 @@ impl CharacterMatrixEngine {
         let mut matrix = vec![vec![' '; matrix_width]; matrix_height];
         let mut text_regions = Vec::new();
 
+        // Characters per cell, so ones that overwrite each other score lower
+        let grid_position = |obj: &PreciseTextObject| {
+            (
+                (obj.bbox.x0 - min_x) / char_width,
+                (obj.bbox.y0 - min_y) / char_height,
+            )
+        };
+        let mut cell_counts: HashMap<(usize, usize), usize> = HashMap::new();
+        for text_obj in &text_objects {
+            let (grid_x, grid_y) = grid_position(text_obj);
+            *cell_counts
+                .entry((grid_x.round() as usize, grid_y.round() as usize))
+                .or_default() += 1;
+        }
+
         for text_obj in &text_objects {
-            let char_x = ((text_obj.bbox.x0 - min_x) / char_width).round() as usize;
-            let char_y = ((text_obj.bbox.y0 - min_y) / char_height).round() as usize;
+            let (grid_x, grid_y) = grid_position(text_obj);
+            let char_x = grid_x.round() as usize;
+            let char_y = grid_y.round() as usize;
 
             if char_y < matrix_height && char_x < matrix_width {
                 if let Some(ch) = text_obj.text.chars().next() {
@@ -1002,9 +1940,16 @@ impl CharacterMatrixEngine {
                             width: 1,
                             height: 1,
                         },
-                        confidence: 1.0,
+                        confidence: placement_confidence(
+                            ch,
+                            grid_x,
+                            grid_y,
+                            cell_counts[&(char_x, char_y)],
+                        ),
                         text_content: ch.to_string(),
                         region_id: text_regions.len(),
+                        block_type: BlockType::default(),
+                        table: None,
                     });
                 }
             }
@@ -1068,8 +2013,9 @@ impl CharacterMatrixEngine {
 
         for (i, region) in char_matrix.text_regions.iter().enumerate() {
             result.push_str(&format!(
-                "Region {}: ({},{}) {}x{} conf:{:.2} - \"{}\"\n",
+                "Region {} [{}]: ({},{}) {}x{} conf:{:.2} - \"{}\"\n",
                 i + 1,
+                region.block_type.label(),
                 region.bbox.x,
                 region.bbox.y,
                 region.bbox.width,
@@ -1082,11 +2028,36 @@ impl CharacterMatrixEngine {
         result
     }
 
-    pub fn run_ferrules_integration_test(&self, pdf_path: &PathBuf) -> Result<String> {
-        use std::process::Command;
+    /// Whether the Ferrules integration binary lists a `--page` option. Older
+    /// builds only take the PDF path, so the flag is passed only when it's known.
+    pub fn ferrules_supports_page() -> bool {
+        static SUPPORTED: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+        *SUPPORTED.get_or_init(|| {
+            Command::new(FERRULES_TEST_BINARY)
+                .arg("--help")
+                .env("DYLD_LIBRARY_PATH", "./lib")
+                .output()
+                .map(|output| {
+                    String::from_utf8_lossy(&output.stdout).contains("--page")
+                        || String::from_utf8_lossy(&output.stderr).contains("--page")
+                })
+                .unwrap_or(false)
+        })
+    }
 
-        let output = Command::new("./target/release/test_ferrules_integration")
-            .arg(pdf_path.to_str().unwrap_or(""))
+    /// Run the Ferrules integration binary, on one 0-based page if `page` is
+    /// given. Check `ferrules_supports_page` first.
+    pub fn run_ferrules_integration_test(
+        &self,
+        pdf_path: &PathBuf,
+        page: Option<usize>,
+    ) -> Result<String> {
+        let mut command = Command::new(FERRULES_TEST_BINARY);
+        command.arg(pdf_path.to_str().unwrap_or(""));
+        if let Some(page) = page {
+            command.arg("--page").arg((page + 1).to_string());
+        }
+        let output = command
             .env("RUST_LOG", "debug")
             .env("DYLD_LIBRARY_PATH", "./lib")
             .output()
@@ -1101,7 +2072,7 @@ impl CharacterMatrixEngine {
                     line.trim_start()
                         .chars()
                         .next()
-                        .map_or(false, |c| c.is_ascii_digit())
+                        .is_some_and(|c| c.is_ascii_digit())
                 })
                 .collect::<Vec<_>>()
                 .join("\n");
@@ -1195,7 +2166,61 @@ impl Default for CharacterMatrixEngine {
     }
 }
 
-// ============= APPLICATION =============
+// ============= SETTINGS =============
+/// Layout state remembered across runs in `~/.chonker/gui.json`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct GuiSettings {
+    split_ratio: f32,
+    active_tab: ExtractionTab,
+    zoom_level: f32,
+    show_bounding_boxes: bool,
+    pdf_dark_mode: bool,
+    /// Multiplier on the display's own scale
+    ui_scale: f32,
+    high_contrast: bool,
+}
+
+impl Default for GuiSettings {
+    fn default() -> Self {
+        Self {
+            split_ratio: 0.5,
+            active_tab: ExtractionTab::RawText,
+            zoom_level: 1.0,
+            show_bounding_boxes: true,
+            pdf_dark_mode: true,
+            ui_scale: 1.0,
+            high_contrast: false,
+        }
+    }
+}
+
+impl GuiSettings {
+    fn path() -> Option<PathBuf> {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".chonker").join("gui.json"))
+    }
+
+    /// Saved settings, or the defaults if there are none or they can't be read.
+    fn load() -> Self {
+        Self::path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) -> Result<()> {
+        let Some(path) = Self::path() else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+// ============= APPLICATION =============
 #[derive(Default)]
 struct ExtractionResult {
     character_matrix: Option<CharacterMatrix>,
@@ -1206,6 +2231,51 @@ struct ExtractionResult {
     original_matrix: Option<Vec<Vec<char>>>,
 }
 
+/// Everything that belongs to one open PDF. The active tab's document lives
+/// in the app's own fields; the others are parked here until switched to.
+#[derive(Default)]
+struct DocumentState {
+    pdf_path: Option<PathBuf>,
+    current_page: usize,
+    total_pages: usize,
+    pdf_texture: Option<egui::TextureHandle>,
+    page_textures: HashMap<usize, Option<egui::TextureHandle>>,
+    page_textures_key: (f32, bool),
+    page_range: String,
+    matrix_result: ExtractionResult,
+    ferrules_output_cache: Option<String>,
+    ferrules_matrix_grid: Option<MatrixGrid>,
+    ferrules_source: Option<(PathBuf, usize)>,
+    raw_text_matrix_grid: Option<MatrixGrid>,
+    extraction: Option<ExtractionTask>,
+    selected_region: Option<usize>,
+    table_editor: Option<usize>,
+    annotations: Annotations,
+    selected_annotation: Option<usize>,
+    selected_cell: Option<(usize, usize)>,
+    selection_start: Option<(usize, usize)>,
+    selection_end: Option<(usize, usize)>,
+    visible_page_changed: Option<Instant>,
+}
+
+/// What happens to a tab with unsaved edits once the user agrees to lose them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum DiscardAction {
+    Close,
+    Reload,
+}
+
+/// Extraction of one page running on the Tokio runtime. Dropping the result
+/// receiver or aborting the handle is enough to cancel it.
+struct ExtractionTask {
+    pdf_path: PathBuf,
+    page: usize,
+    started: Instant,
+    handle: tokio::task::JoinHandle<()>,
+    progress: tokio::sync::watch::Receiver<&'static str>,
+    result: mpsc::Receiver<Result<CharacterMatrix, String>>,
+}
+
 struct Chonker5App {
     // PDF state
     pdf_path: Option<PathBuf>,
@@ -1215,6 +2285,13 @@ struct Chonker5App {
     pdf_texture: Option<egui::TextureHandle>,
     needs_render: bool,
 
+    // Continuous scroll view; None marks a page that failed to render
+    continuous_scroll: bool,
+    page_textures: HashMap<usize, Option<egui::TextureHandle>>,
+    page_textures_key: (f32, bool),
+    scroll_to_page: Option<usize>,
+    visible_page_changed: Option<Instant>,
+
     // UI assets
     hamster_texture: Option<egui::TextureHandle>,
 
@@ -1230,13 +2307,19 @@ struct Chonker5App {
     ferrules_binary: Option<PathBuf>,
     ferrules_output_cache: Option<String>,
     ferrules_matrix_grid: Option<MatrixGrid>,
+    // (PDF, 0-based page) the Ferrules grid was run on, if it targeted one
+    ferrules_source: Option<(PathBuf, usize)>,
+
+    // Engine comparison, rebuilt when the page changes
+    comparison: Option<EngineComparison>,
+    comparison_task: Option<ComparisonTask>,
 
     // Raw text matrix grid
     raw_text_matrix_grid: Option<MatrixGrid>,
 
     // Async runtime
     runtime: Arc<tokio::runtime::Runtime>,
-    vision_receiver: Option<mpsc::Receiver<Result<CharacterMatrix, String>>>,
+    extraction: Option<ExtractionTask>,
 
     // File dialog
     file_dialog_receiver: Option<std::sync::mpsc::Receiver<Option<PathBuf>>>,
@@ -1256,12 +2339,87 @@ struct Chonker5App {
     is_dragging: bool,
     clipboard: String,
     first_frame: bool,
+    recent: RecentFiles,
+
+    // Region box editing on the page
+    region_edit_mode: bool,
+    selected_region: Option<usize>,
+    region_drag: Option<RegionDrag>,
+    table_editor: Option<usize>,
+    // Typed table edits waiting on EDIT_SAVE_DELAY: (edited, page, regions)
+    region_edits: Option<(Instant, usize, Vec<TextRegion>)>,
+
+    // Review annotations drawn on the page
+    annotate_mode: bool,
+    annotations: Annotations,
+    selected_annotation: Option<usize>,
+    annotation_drag: Option<((usize, usize), (usize, usize))>,
+    notes_edited: Option<Instant>,
+
+    // Block list; clicking a block zooms the page onto it
+    show_block_list: bool,
+    scroll_to_region: Option<usize>,
+
+    // Find and replace
+    search: SearchState,
+
+    // Matrix text drawn over the page image
+    text_overlay: bool,
+    text_overlay_opacity: f32,
+
+    // Confidence heatmap; cells at or above the threshold are hidden
+    show_heatmap: bool,
+    heatmap_on_page: bool,
+    heatmap_threshold: f32,
+
+    // Export
+    export_dialog: ExportDialog,
+    export_folder_receiver: Option<std::sync::mpsc::Receiver<Option<PathBuf>>>,
+    export_receiver: Option<std::sync::mpsc::Receiver<Result<Vec<PathBuf>, String>>>,
+
+    // Matrix view font
+    matrix_font_size: f32,
+    matrix_font_path: Option<PathBuf>,
+    font_dialog_receiver: Option<std::sync::mpsc::Receiver<Option<PathBuf>>>,
+
+    // Accessibility
+    ui_scale: f32,
+    applied_pixels_per_point: Option<f32>,
+    high_contrast: bool,
+
+    // Layout as last written to disk
+    saved_settings: GuiSettings,
+
+    // Document tabs; the active slot is an empty placeholder
+    documents: Vec<DocumentState>,
+    active_document: usize,
+    // Tab waiting on a discard-or-cancel answer because it has unsaved edits
+    pending_discard: Option<(PathBuf, DiscardAction)>,
 }
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
 enum ExtractionTab {
     RawText,
     SmartLayout,
+    Compare,
+}
+
+/// PDFium-only matrix beside the Smart Layout matrix for one page.
+struct EngineComparison {
+    pdf_path: PathBuf,
+    page: usize,
+    /// (PDFium, Smart Layout) grids, tinted where their cells differ
+    grids: Result<(MatrixGrid, MatrixGrid), String>,
+    changed_cells: usize,
+}
+
+/// Both engines running for a comparison on the Tokio runtime.
+struct ComparisonTask {
+    pdf_path: PathBuf,
+    page: usize,
+    handle: tokio::task::JoinHandle<()>,
+    /// (PDFium, Smart Layout) matrices
+    result: mpsc::Receiver<Result<(Vec<Vec<char>>, Vec<Vec<char>>), String>>,
 }
 
 #[derive(PartialEq, Clone, Copy, Debug)]
@@ -1270,6 +2428,23 @@ enum FocusedPane {
     MatrixView,
 }
 
+/// In-progress edit of a region box, in matrix cells (column, row).
+#[derive(Clone, Debug)]
+enum RegionDrag {
+    Draw {
+        start: (usize, usize),
+        end: (usize, usize),
+    },
+    Move {
+        index: usize,
+        grab: (usize, usize),
+        original: CharBBox,
+    },
+    Resize {
+        index: usize,
+    },
+}
+
 #[derive(Clone, Copy, Debug)]
 enum DragAction {
     StartDrag(usize, usize),
@@ -1302,6 +2477,10 @@ impl Chonker5App {
             None
         };
 
+        if let Err(e) = Self::install_matrix_font(&cc.egui_ctx, None) {
+            tracing::error!("Failed to set up matrix font: {}", e);
+        }
+
         let mut app = Self {
             pdf_path: None,
             current_page: 0,
@@ -1309,6 +2488,11 @@ impl Chonker5App {
             zoom_level: 1.0,
             pdf_texture: None,
             needs_render: false,
+            continuous_scroll: false,
+            page_textures: HashMap::new(),
+            page_textures_key: (1.0, true),
+            scroll_to_page: None,
+            visible_page_changed: None,
             hamster_texture,
             page_range: "1-10".to_string(),
             matrix_result: Default::default(),
@@ -1316,9 +2500,10 @@ impl Chonker5App {
             ferrules_binary: None,
             ferrules_output_cache: None,
             ferrules_matrix_grid: None,
+            ferrules_source: None,
             raw_text_matrix_grid: None,
             runtime,
-            vision_receiver: None,
+            extraction: None,
             file_dialog_receiver: None,
             file_dialog_pending: false,
             log_messages: vec![
@@ -1329,6 +2514,8 @@ impl Chonker5App {
             show_bounding_boxes: true,
             split_ratio: 0.5,
             matrix_engine: CharacterMatrixEngine::new(),
+            comparison: None,
+            comparison_task: None,
             selected_cell: None,
             pdf_dark_mode: true,
             focused_pane: FocusedPane::PdfView,
@@ -1337,12 +2524,104 @@ impl Chonker5App {
             is_dragging: false,
             clipboard: String::new(),
             first_frame: true,
+            recent: RecentFiles::load(),
+            region_edit_mode: false,
+            selected_region: None,
+            region_drag: None,
+            table_editor: None,
+            region_edits: None,
+            annotate_mode: false,
+            annotations: Annotations::default(),
+            selected_annotation: None,
+            annotation_drag: None,
+            notes_edited: None,
+            show_block_list: false,
+            scroll_to_region: None,
+            search: SearchState::default(),
+            text_overlay: false,
+            text_overlay_opacity: 0.6,
+            show_heatmap: false,
+            heatmap_on_page: false,
+            heatmap_threshold: 0.9,
+            export_dialog: ExportDialog {
+                open: false,
+                kind: ExportKind::Document(export::ExportFormat::Markdown),
+                destination: None,
+                include_notes: false,
+            },
+            export_folder_receiver: None,
+            export_receiver: None,
+            matrix_font_size: 9.0,
+            matrix_font_path: None,
+            font_dialog_receiver: None,
+            ui_scale: 1.0,
+            applied_pixels_per_point: None,
+            high_contrast: false,
+            saved_settings: GuiSettings::default(),
+            documents: vec![DocumentState::default()],
+            active_document: 0,
+            pending_discard: None,
         };
 
+        app.apply_settings(GuiSettings::load());
         app.init_ferrules_binary();
         app
     }
 
+    fn settings(&self) -> GuiSettings {
+        GuiSettings {
+            split_ratio: self.split_ratio,
+            active_tab: self.active_tab.clone(),
+            zoom_level: self.zoom_level,
+            show_bounding_boxes: self.show_bounding_boxes,
+            pdf_dark_mode: self.pdf_dark_mode,
+            ui_scale: self.ui_scale,
+            high_contrast: self.high_contrast,
+        }
+    }
+
+    fn apply_settings(&mut self, settings: GuiSettings) {
+        self.split_ratio = settings.split_ratio.clamp(0.2, 0.8);
+        self.active_tab = settings.active_tab.clone();
+        self.zoom_level = settings.zoom_level.clamp(0.5, 3.0);
+        self.show_bounding_boxes = settings.show_bounding_boxes;
+        self.pdf_dark_mode = settings.pdf_dark_mode;
+        self.ui_scale = settings.ui_scale.clamp(UI_SCALE_MIN, UI_SCALE_MAX);
+        self.high_contrast = settings.high_contrast;
+        self.saved_settings = settings;
+    }
+
+    /// Scale the whole UI relative to the display, once the scale slider is let go.
+    fn apply_ui_scale(&mut self, ctx: &egui::Context) {
+        let native = ctx.native_pixels_per_point().unwrap_or(1.0);
+        let current = ctx.pixels_per_point();
+        // eframe's Ctrl+Plus/Minus zoom changes the scale behind our back; keep the setting in step
+        if self.applied_pixels_per_point.is_some_and(|applied| (applied - current).abs() > 0.001) {
+            self.ui_scale = (current / native).clamp(UI_SCALE_MIN, UI_SCALE_MAX);
+        }
+        if ctx.input(|i| i.pointer.any_down()) {
+            self.applied_pixels_per_point = Some(current);
+            return;
+        }
+        let pixels_per_point = native * self.ui_scale;
+        if (current - pixels_per_point).abs() > 0.001 {
+            ctx.set_pixels_per_point(pixels_per_point);
+        }
+        self.applied_pixels_per_point = Some(pixels_per_point);
+    }
+
+    /// Write the layout out once it changes, waiting for drags to finish.
+    fn save_settings_if_changed(&mut self, ctx: &egui::Context) {
+        let settings = self.settings();
+        if settings == self.saved_settings || ctx.input(|i| i.pointer.any_down()) {
+            return;
+        }
+        if let Err(e) = settings.save() {
+            self.log(&format!("⚠️ Could not save layout settings: {}", e));
+        }
+        self.saved_settings = settings;
+    }
+
     fn init_ferrules_binary(&mut self) {
         self.log("🔄 Looking for Ferrules binary...");
 
@@ -1380,6 +2659,65 @@ impl Chonker5App {
         }
     }
 
+    /// Register the matrix font family, backed by a user-picked font file with
+    /// the built-in monospace fonts as fallback for glyphs the file lacks.
+    fn install_matrix_font(ctx: &egui::Context, path: Option<&Path>) -> Result<()> {
+        let mut fonts = egui::FontDefinitions::default();
+        let mut family = fonts
+            .families
+            .get(&egui::FontFamily::Monospace)
+            .cloned()
+            .unwrap_or_default();
+        if let Some(path) = path {
+            let data = std::fs::read(path)?;
+            fonts
+                .font_data
+                .insert("matrix_font".to_string(), egui::FontData::from_owned(data));
+            family.insert(0, "matrix_font".to_string());
+        }
+        fonts
+            .families
+            .insert(egui::FontFamily::Name(MATRIX_FONT_FAMILY.into()), family);
+        ctx.set_fonts(fonts);
+        Ok(())
+    }
+
+    fn pick_matrix_font(&mut self, ctx: &egui::Context) {
+        if self.font_dialog_receiver.is_some() {
+            return;
+        }
+
+        let ctx_clone = ctx.clone();
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.font_dialog_receiver = Some(rx);
+
+        std::thread::spawn(move || {
+            let result = rfd::FileDialog::new()
+                .add_filter("Fonts", &["ttf", "otf"])
+                .pick_file();
+
+            let _ = tx.send(result);
+            ctx_clone.request_repaint();
+        });
+    }
+
+    fn process_font_dialog_result(&mut self, ctx: &egui::Context) {
+        let Some(Ok(result)) = self.font_dialog_receiver.as_ref().map(|rx| rx.try_recv()) else {
+            return;
+        };
+        self.font_dialog_receiver = None;
+
+        if let Some(path) = result {
+            match Self::install_matrix_font(ctx, Some(&path)) {
+                Ok(()) => {
+                    self.log(&format!("🔤 Matrix font: {}", path.display()));
+                    self.matrix_font_path = Some(path);
+                }
+                Err(e) => self.log(&format!("❌ Failed to load font: {}", e)),
+            }
+        }
+    }
+
     fn open_file(&mut self, ctx: &egui::Context) {
         if self.file_dialog_pending {
             self.log("📂 File dialog already in progress...");
@@ -1403,6 +2741,151 @@ impl Chonker5App {
         });
     }
 
+    /// Open a PDF from the file dialog, the recent list or a drop, resuming at
+    /// the page it was last left on.
+    fn load_pdf(&mut self, ctx: &egui::Context, path: PathBuf) {
+        if !path.exists() {
+            self.log("❌ File does not exist");
+            return;
+        }
+
+        if !path.is_file() {
+            self.log("❌ Selection is not a file");
+            return;
+        }
+
+        if !path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("pdf"))
+        {
+            self.log("❌ File is not a PDF");
+            return;
+        }
+
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+        let same_file = |p: &Option<PathBuf>| {
+            p.as_ref().and_then(|p| p.canonicalize().ok()).as_ref() == Some(&canonical)
+        };
+        let reload = same_file(&self.pdf_path);
+        if let (true, true, Some(open_path)) =
+            (reload, self.matrix_result.matrix_dirty, &self.pdf_path)
+        {
+            self.pending_discard = Some((open_path.clone(), DiscardAction::Reload));
+            return;
+        }
+        if !reload {
+            if let Some(index) = self.documents.iter().position(|doc| same_file(&doc.pdf_path)) {
+                self.switch_document(index);
+                self.log("📑 Switched to the tab that already has this file");
+                return;
+            }
+        }
+
+        // Open it before touching any tab, so a bad file leaves nothing behind
+        let pages = match self.get_pdf_info(&path) {
+            Ok(pages) => pages,
+            Err(e) => {
+                self.log(&format!("❌ Failed to load PDF: {}", e));
+                return;
+            }
+        };
+        if reload {
+            self.log("📑 Already open; reloading");
+        } else {
+            self.open_document_tab();
+        }
+
+        self.flush_annotations();
+        self.flush_region_edits();
+        self.cancel_extraction();
+        self.pdf_path = Some(path.clone());
+        self.current_page = 0;
+        self.pdf_texture = None;
+        self.page_textures.clear();
+        self.scroll_to_page = Some(0);
+        self.matrix_result.character_matrix = None;
+        self.ferrules_output_cache = None;
+        self.ferrules_matrix_grid = None;
+        self.raw_text_matrix_grid = None;
+        self.selected_annotation = None;
+        self.annotations = match Annotations::load(&path) {
+            Ok(annotations) => annotations,
+            Err(e) => {
+                self.log(&format!("⚠️ Could not read notes: {}", e));
+                Annotations::default()
+            }
+        };
+
+        self.total_pages = pages;
+        self.log(&format!("✅ Loaded PDF: {} ({} pages)", path.display(), pages));
+
+        if let Some(entry) = self.recent.get(&path) {
+            self.current_page = entry.last_page.min(pages.saturating_sub(1));
+            self.scroll_to_page = Some(self.current_page);
+        }
+        if let Err(e) = self.recent.touch(&path, self.current_page) {
+            self.log(&format!("⚠️ Could not update recent files: {}", e));
+        }
+
+        if pages > 20 {
+            self.page_range = "1-10".to_string();
+            self.log("📄 Large PDF detected - Default page range set to 1-10");
+        } else {
+            self.page_range.clear();
+        }
+
+        if let Err(e) = self.safe_render_current_page(ctx) {
+            self.log(&format!("⚠️ Could not render page: {}", e));
+        }
+
+        self.log("🚀 Starting character matrix extraction...");
+        if let Err(e) = self.safe_extract_character_matrix(ctx) {
+            self.log(&format!("❌ Matrix extraction failed: {}", e));
+        } else {
+            self.active_tab = ExtractionTab::RawText;
+        }
+    }
+
+    /// Open the first PDF dropped onto the window.
+    fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        if ctx.input(|i| !i.raw.hovered_files.is_empty()) {
+            let painter = ctx.layer_painter(egui::LayerId::new(
+                egui::Order::Foreground,
+                egui::Id::new("file_drop"),
+            ));
+            let rect = ctx.screen_rect();
+            painter.rect_filled(rect, 0.0, Color32::from_black_alpha(180));
+            painter.text(
+                rect.center(),
+                Align2::CENTER_CENTER,
+                "Drop a PDF to open it",
+                FontId::monospace(18.0),
+                palette().highlight,
+            );
+        }
+
+        let dropped: Vec<PathBuf> = ctx.input(|i| {
+            i.raw
+                .dropped_files
+                .iter()
+                .filter_map(|file| file.path.clone())
+                .collect()
+        });
+        if dropped.is_empty() {
+            return;
+        }
+        match dropped.into_iter().find(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("pdf"))
+        }) {
+            Some(path) => {
+                self.log(&format!("📥 Dropped file: {}", path.display()));
+                self.load_pdf(ctx, path);
+            }
+            None => self.log("❌ Dropped file is not a PDF"),
+        }
+    }
+
     fn process_file_dialog_result(&mut self, ctx: &egui::Context) {
         if let Some(receiver) = &self.file_dialog_receiver {
             if let Ok(file_result) = receiver.try_recv() {
@@ -1412,64 +2895,7 @@ impl Chonker5App {
                 match file_result {
                     Some(path) => {
                         self.log(&format!("📂 Selected file: {}", path.display()));
-
-                        if !path.exists() {
-                            self.log("❌ File does not exist");
-                            return;
-                        }
-
-                        if !path.is_file() {
-                            self.log("❌ Selection is not a file");
-                            return;
-                        }
-
-                        if path.extension().and_then(|ext| ext.to_str()) != Some("pdf") {
-                            self.log("❌ File is not a PDF");
-                            return;
-                        }
-
-                        self.pdf_path = Some(path.clone());
-                        self.current_page = 0;
-                        self.pdf_texture = None;
-                        self.matrix_result.character_matrix = None;
-                        self.ferrules_output_cache = None;
-                        self.ferrules_matrix_grid = None;
-                        self.raw_text_matrix_grid = None;
-
-                        match self.get_pdf_info(&path) {
-                            Ok(pages) => {
-                                self.total_pages = pages;
-                                self.log(&format!(
-                                    "✅ Loaded PDF: {} ({} pages)",
-                                    path.display(),
-                                    pages
-                                ));
-
-                                if pages > 20 {
-                                    self.page_range = "1-10".to_string();
-                                    self.log(
-                                        "📄 Large PDF detected - Default page range set to 1-10",
-                                    );
-                                } else {
-                                    self.page_range.clear();
-                                }
-
-                                if let Err(e) = self.safe_render_current_page(ctx) {
-                                    self.log(&format!("⚠️ Could not render page: {}", e));
-                                }
-
-                                self.log("🚀 Starting character matrix extraction...");
-                                if let Err(e) = self.safe_extract_character_matrix(ctx) {
-                                    self.log(&format!("❌ Matrix extraction failed: {}", e));
-                                } else {
-                                    self.active_tab = ExtractionTab::RawText;
-                                }
-                            }
-                            Err(e) => {
-                                self.log(&format!("❌ Failed to load PDF: {}", e));
-                                self.pdf_path = None;
-                            }
-                        }
+                        self.load_pdf(ctx, path);
                     }
                     None => {
                         self.log("📂 File selection cancelled");
@@ -1493,7 +2919,7 @@ impl Chonker5App {
             return Err(anyhow::anyhow!("No PDF loaded"));
         }
 
-        if self.vision_receiver.is_some() {
+        if self.extraction.is_some() {
             return Err(anyhow::anyhow!("Extraction already in progress"));
         }
 
@@ -1506,8 +2932,18 @@ impl Chonker5App {
     }
 
     fn get_pdf_info(&self, path: &PathBuf) -> Result<usize> {
+        match bind_pdfium().and_then(|pdfium| {
+            let pages = pdfium.load_pdf_from_file(path, None)?.pages().len() as usize;
+            Ok(pages)
+        }) {
+            Ok(pages) => return Ok(pages),
+            Err(e) => tracing::warn!("PDFium couldn't read the page count ({}), trying mutool", e),
+        }
+
         if Command::new("mutool").arg("--version").output().is_err() {
-            return Err(anyhow::anyhow!("mutool not found - install mupdf-tools"));
+            return Err(anyhow::anyhow!(
+                "PDFium is unavailable and mutool is not installed (mupdf-tools)"
+            ));
         }
 
         let output = Command::new("mutool").arg("info").arg(path).output()?;
@@ -1528,101 +2964,311 @@ impl Chonker5App {
     }
 
     fn render_current_page(&mut self, ctx: &egui::Context) {
-        if let Some(pdf_path) = &self.pdf_path {
-            let temp_png =
-                std::env::temp_dir().join(format!("chonker5_page_{}.png", self.current_page));
-            let dpi = 150.0 * self.zoom_level;
-
-            let result = Command::new("mutool")
-                .arg("draw")
-                .arg("-o")
-                .arg(&temp_png)
-                .arg("-r")
-                .arg(dpi.to_string())
-                .arg("-F")
-                .arg("png")
-                .arg(pdf_path)
-                .arg(format!("{}", self.current_page + 1))
-                .output();
+        if let Some(texture) = self.render_page_texture(ctx, self.current_page) {
+            self.pdf_texture = Some(texture);
+            self.log(&format!(
+                "📄 Rendered page {} {}",
+                self.current_page + 1,
+                if self.pdf_dark_mode { "🌙" } else { "" }
+            ));
+        }
+    }
 
-            match result {
-                Ok(output) => {
-                    if output.status.success() {
-                        if let Ok(image_data) = std::fs::read(&temp_png) {
-                            if let Ok(mut image) = image::load_from_memory(&image_data) {
-                                if self.pdf_dark_mode {
-                                    let mut rgba_image = image.to_rgba8();
-                                    image::imageops::colorops::invert(&mut rgba_image);
-                                    image = image::DynamicImage::ImageRgba8(rgba_image);
-                                }
+    fn render_page_texture(
+        &mut self,
+        ctx: &egui::Context,
+        page: usize,
+    ) -> Option<egui::TextureHandle> {
+        let pdf_path = self.pdf_path.clone()?;
+        let dpi = 150.0 * self.zoom_level;
+
+        let mut image = match render_page_image(&pdf_path, page, dpi) {
+            Ok(image) => image,
+            Err(e) => {
+                self.log(&format!("❌ Failed to render page: {}", e));
+                return None;
+            }
+        };
+        if self.pdf_dark_mode {
+            let mut rgba_image = image.to_rgba8();
+            smart_invert(&mut rgba_image);
+            image = image::DynamicImage::ImageRgba8(rgba_image);
+        }
 
-                                let size = [image.width() as _, image.height() as _];
-                                let image_buffer = image.to_rgba8();
-                                let pixels = image_buffer.as_flat_samples();
+        let size = [image.width() as _, image.height() as _];
+        let image_buffer = image.to_rgba8();
+        let pixels = image_buffer.as_flat_samples();
+        let color_image = egui::ColorImage::from_rgba_unmultiplied(size, pixels.as_slice());
+        Some(ctx.load_texture(
+            format!("pdf_page_{}", page),
+            color_image,
+            Default::default(),
+        ))
+    }
 
-                                let color_image = egui::ColorImage::from_rgba_unmultiplied(
-                                    size,
-                                    pixels.as_slice(),
-                                );
-                                self.pdf_texture = Some(ctx.load_texture(
-                                    format!("pdf_page_{}", self.current_page),
-                                    color_image,
-                                    Default::default(),
-                                ));
-
-                                self.log(&format!(
-                                    "📄 Rendered page {} {}",
-                                    self.current_page + 1,
-                                    if self.pdf_dark_mode { "🌙" } else { "" }
-                                ));
-                            }
+    fn show_continuous_pages(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let cache_key = (self.zoom_level, self.pdf_dark_mode);
+        if self.page_textures_key != cache_key {
+            self.page_textures.clear();
+            self.page_textures_key = cache_key;
+        }
+
+        // Every page is laid out at the size of the first rendered one, so
+        // pages that haven't scrolled into view yet get a placeholder
+        let page_size = self
+            .page_textures
+            .values()
+            .flatten()
+            .next()
+            .or(self.pdf_texture.as_ref())
+            .map(|texture| texture.size_vec2())
+            .unwrap_or(egui::vec2(8.5, 11.0) * 150.0 * self.zoom_level);
+        let base_scale = ((ui.available_width() - PAGE_GAP) / page_size.x).min(1.0);
+        let display_size = page_size * base_scale * self.zoom_level;
+        let row_height = display_size.y + PAGE_GAP;
+        let row_stride = row_height + ui.spacing().item_spacing.y;
+
+        let mut scroll_area = egui::ScrollArea::both()
+            .auto_shrink([false; 2])
+            .id_source("continuous_pages");
+        if let Some(page) = self.scroll_to_page.take() {
+            scroll_area = scroll_area.vertical_scroll_offset(page as f32 * row_stride);
+        }
+
+        let mut missing = None;
+        let output = scroll_area.show_rows(ui, row_height, self.total_pages, |ui, visible| {
+            for page in visible.clone() {
+                let width = ui.available_width().max(display_size.x);
+                let (row_rect, response) =
+                    ui.allocate_exact_size(egui::vec2(width, row_height), Sense::click());
+                let page_rect = Rect::from_min_size(
+                    egui::pos2(row_rect.center().x - display_size.x / 2.0, row_rect.top()),
+                    display_size,
+                );
+
+                match self.page_textures.get(&page) {
+                    Some(Some(texture)) => {
+                        ui.painter().image(
+                            texture.id(),
+                            page_rect,
+                            Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                            Color32::WHITE,
+                        );
+                    }
+                    rendered => {
+                        ui.painter()
+                            .rect_filled(page_rect, 0.0, Color32::from_rgb(20, 25, 30));
+                        let label = if rendered.is_some() {
+                            format!("Page {} could not be rendered", page + 1)
+                        } else {
+                            missing.get_or_insert(page);
+                            format!("Page {}", page + 1)
+                        };
+                        ui.painter().text(
+                            page_rect.center(),
+                            Align2::CENTER_CENTER,
+                            label,
+                            FontId::monospace(12.0),
+                            palette().dim,
+                        );
+                    }
+                }
+
+                if page == self.current_page {
+                    if let Some(index) = self.scroll_to_region.take() {
+                        if let Some(rect) = self.region_screen_rect(index, page_rect) {
+                            ui.scroll_to_rect(rect, Some(egui::Align::Center));
                         }
+                    }
+                }
+                if page == self.current_page && self.show_heatmap && self.heatmap_on_page {
+                    self.draw_confidence_heatmap(ui, page_rect);
+                }
+                if page == self.current_page && self.text_overlay {
+                    self.draw_matrix_text_overlay(ui, page_rect);
+                }
+                if page == self.current_page && self.show_bounding_boxes {
+                    if self.region_edit_mode {
+                        self.handle_region_editing(ui, page_rect);
+                    }
+                    self.draw_character_matrix_overlay(ui, page_rect);
+                }
 
-                        let _ = std::fs::remove_file(&temp_png);
-                    } else {
-                        let stderr = String::from_utf8_lossy(&output.stderr);
-                        self.log(&format!("❌ Failed to render page: {}", stderr));
+                if response.hovered() {
+                    let zoom_delta = ui.input(|i| i.zoom_delta());
+                    if zoom_delta != 1.0 {
+                        self.zoom_level = (self.zoom_level * zoom_delta).clamp(0.5, 3.0);
                     }
                 }
-                Err(e) => {
-                    self.log(&format!("❌ Failed to run mutool: {}", e));
+            }
+            visible
+        });
+
+        // Render one page per frame so scrolling stays responsive
+        if let Some(page) = missing {
+            let texture = self.render_page_texture(ctx, page);
+            self.page_textures.insert(page, texture);
+            ctx.request_repaint();
+        }
+        let visible = output.inner;
+        self.page_textures.retain(|page, _| {
+            *page + PAGE_PRELOAD >= visible.start && *page < visible.end + PAGE_PRELOAD
+        });
+
+        // The page under the middle of the viewport drives the indicator and the matrix
+        let viewport = output.inner_rect;
+        let middle = output.state.offset.y + viewport.height() / 2.0;
+        let visible_page =
+            ((middle / row_stride) as usize).min(self.total_pages.saturating_sub(1));
+        if visible_page != self.current_page {
+            self.cancel_extraction();
+            self.current_page = visible_page;
+            self.matrix_result.character_matrix = None;
+            self.ferrules_output_cache = None;
+            self.ferrules_matrix_grid = None;
+            self.visible_page_changed = Some(Instant::now());
+        }
+
+        // Only re-extract once scrolling has settled on a page
+        if let Some(changed) = self.visible_page_changed {
+            if changed.elapsed() >= PAGE_SETTLE {
+                self.visible_page_changed = None;
+                self.pdf_texture = self.page_textures.get(&self.current_page).cloned().flatten();
+                self.extract_character_matrix(ctx);
+            } else {
+                ctx.request_repaint_after(PAGE_SETTLE);
+            }
+        }
+
+        let indicator = format!(" {} / {} ", self.current_page + 1, self.total_pages);
+        let painter = ui.painter();
+        let galley = painter.layout_no_wrap(indicator, FontId::monospace(12.0), palette().fg);
+        let indicator_rect = Rect::from_min_size(
+            egui::pos2(
+                viewport.right() - galley.size().x - 16.0,
+                viewport.top() + 8.0,
+            ),
+            galley.size() + egui::vec2(4.0, 4.0),
+        );
+        painter.rect_filled(indicator_rect, 2.0, palette().bg.gamma_multiply(0.85));
+        painter.rect_stroke(indicator_rect, 2.0, Stroke::new(1.0, palette().chrome));
+        painter.galley(indicator_rect.min + egui::vec2(2.0, 2.0), galley);
+    }
+
+    /// Jump to a page (clamped to the document) and re-extract it.
+    fn go_to_page(&mut self, ctx: &egui::Context, page: usize) {
+        let page = page.min(self.total_pages.saturating_sub(1));
+        if page == self.current_page {
+            return;
+        }
+        self.current_page = page;
+        self.scroll_to_page = Some(page);
+        self.matrix_result.character_matrix = None;
+        self.ferrules_output_cache = None;
+        self.ferrules_matrix_grid = None;
+        self.render_current_page(ctx);
+        self.extract_character_matrix(ctx);
+        if let Some(path) = &self.pdf_path {
+            let _ = self.recent.touch(path, page);
+        }
+    }
+
+    /// Page, zoom, pane-size and search keys. Matrix cursor keys live in `MatrixGrid`.
+    fn handle_navigation_keys(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        let (keys, modifiers) = ctx.input(|i| {
+            let keys: Vec<egui::Key> = i
+                .events
+                .iter()
+                .filter_map(|event| match event {
+                    egui::Event::Key {
+                        key, pressed: true, ..
+                    } => Some(*key),
+                    _ => None,
+                })
+                .collect();
+            (keys, i.modifiers)
+        });
+
+        let pdf_keys = self.focused_pane == FocusedPane::PdfView && self.pdf_path.is_some();
+        for key in keys {
+            match key {
+                egui::Key::F3 => {
+                    self.search.open = true;
+                    self.search.pending = Some(if modifiers.shift {
+                        SearchAction::Previous
+                    } else {
+                        SearchAction::Next
+                    });
+                }
+                // Alt rather than Ctrl+Plus/Minus, which eframe keeps for UI zoom
+                egui::Key::ArrowLeft if modifiers.alt => {
+                    self.split_ratio = (self.split_ratio - 0.05).clamp(0.2, 0.8);
+                }
+                egui::Key::ArrowRight if modifiers.alt => {
+                    self.split_ratio = (self.split_ratio + 0.05).clamp(0.2, 0.8);
+                }
+                egui::Key::W if modifiers.command || modifiers.ctrl => {
+                    self.close_document(self.active_document)
+                }
+                egui::Key::PageUp if modifiers.command || modifiers.ctrl => {
+                    let count = self.documents.len();
+                    self.switch_document((self.active_document + count - 1) % count);
+                }
+                egui::Key::PageDown if modifiers.command || modifiers.ctrl => {
+                    self.switch_document((self.active_document + 1) % self.documents.len());
+                }
+                _ if !pdf_keys || modifiers.command || modifiers.ctrl => {}
+                egui::Key::ArrowLeft | egui::Key::ArrowUp => {
+                    self.go_to_page(ctx, self.current_page.saturating_sub(1))
+                }
+                egui::Key::ArrowRight | egui::Key::ArrowDown => {
+                    self.go_to_page(ctx, self.current_page + 1)
+                }
+                egui::Key::PageUp => self.go_to_page(ctx, self.current_page.saturating_sub(10)),
+                egui::Key::PageDown => self.go_to_page(ctx, self.current_page + 10),
+                egui::Key::Home => self.go_to_page(ctx, 0),
+                egui::Key::End => self.go_to_page(ctx, usize::MAX),
+                egui::Key::PlusEquals => {
+                    self.zoom_level = (self.zoom_level + 0.25).min(3.0);
+                    self.render_current_page(ctx);
+                }
+                egui::Key::Minus => {
+                    self.zoom_level = (self.zoom_level - 0.25).max(0.5);
+                    self.render_current_page(ctx);
                 }
+                _ => {}
             }
         }
     }
 
     fn extract_character_matrix(&mut self, ctx: &egui::Context) {
-        if self.pdf_path.is_none() {
+        let Some(pdf_path) = self.pdf_path.clone() else {
             self.log("⚠️ No PDF loaded. Open a file first.");
             return;
-        }
+        };
+        let page = self.current_page;
 
-        let pdf_path = match &self.pdf_path {
-            Some(path) => path.clone(),
-            None => {
-                self.log("❌ No PDF file selected");
+        if let Some(task) = &self.extraction {
+            if task.page == page && task.pdf_path == pdf_path {
+                self.log(&format!("⏳ Page {} is already being extracted", page + 1));
                 return;
             }
-        };
-
-        let runtime = self.runtime.clone();
-        let ctx = ctx.clone();
+        }
+        self.cancel_extraction();
 
         self.matrix_result.is_loading = true;
         self.matrix_result.error = None;
-        self.vision_receiver = None;
-
-        self.log(&format!(
-            "🔄 Processing PDF page {}...",
-            self.current_page + 1
-        ));
+        self.log(&format!("🔄 Processing PDF page {}...", page + 1));
 
         let (tx, rx) = mpsc::channel(1);
-        self.vision_receiver = Some(rx);
-
-        let current_page = self.current_page;
-        runtime.spawn(async move {
-            let result = Self::process_pdf_async(pdf_path, current_page).await;
+        let (progress_tx, progress_rx) = tokio::sync::watch::channel("Starting");
+        let ctx = ctx.clone();
+        let task_path = pdf_path.clone();
+        let handle = self.runtime.spawn(async move {
+            let result = Self::process_pdf_async(task_path, page, progress_tx).await;
 
             if let Err(e) = tx.send(result).await {
                 tracing::error!("Failed to send matrix result: {}", e);
@@ -1630,133 +3276,2008 @@ impl Chonker5App {
 
             ctx.request_repaint();
         });
+
+        self.extraction = Some(ExtractionTask {
+            pdf_path,
+            page,
+            started: Instant::now(),
+            handle,
+            progress: progress_rx,
+            result: rx,
+        });
     }
 
-    async fn process_pdf_async(
+    /// Stop the running extraction, e.g. because the user moved to another page.
+    fn cancel_extraction(&mut self) {
+        if let Some(task) = self.extraction.take() {
+            task.handle.abort();
+            self.matrix_result.is_loading = false;
+            self.log(&format!("⏹ Cancelled extraction of page {}", task.page + 1));
+        }
+    }
+
+    fn stash_document(&mut self) -> DocumentState {
+        self.flush_annotations();
+        self.flush_region_edits();
+        DocumentState {
+            pdf_path: self.pdf_path.take(),
+            current_page: std::mem::take(&mut self.current_page),
+            total_pages: std::mem::take(&mut self.total_pages),
+            pdf_texture: self.pdf_texture.take(),
+            page_textures: std::mem::take(&mut self.page_textures),
+            page_textures_key: self.page_textures_key,
+            page_range: std::mem::take(&mut self.page_range),
+            matrix_result: std::mem::take(&mut self.matrix_result),
+            ferrules_output_cache: self.ferrules_output_cache.take(),
+            ferrules_matrix_grid: self.ferrules_matrix_grid.take(),
+            ferrules_source: self.ferrules_source.take(),
+            raw_text_matrix_grid: self.raw_text_matrix_grid.take(),
+            extraction: self.extraction.take(),
+            selected_region: self.selected_region.take(),
+            table_editor: self.table_editor.take(),
+            annotations: std::mem::take(&mut self.annotations),
+            selected_annotation: self.selected_annotation.take(),
+            selected_cell: self.selected_cell.take(),
+            selection_start: self.selection_start.take(),
+            selection_end: self.selection_end.take(),
+            visible_page_changed: self.visible_page_changed.take(),
+        }
+    }
+
+    fn restore_document(&mut self, doc: DocumentState) {
+        self.pdf_path = doc.pdf_path;
+        self.current_page = doc.current_page;
+        self.total_pages = doc.total_pages;
+        self.pdf_texture = doc.pdf_texture;
+        self.page_textures = doc.page_textures;
+        self.page_textures_key = doc.page_textures_key;
+        self.page_range = doc.page_range;
+        self.matrix_result = doc.matrix_result;
+        self.ferrules_output_cache = doc.ferrules_output_cache;
+        self.ferrules_matrix_grid = doc.ferrules_matrix_grid;
+        self.ferrules_source = doc.ferrules_source;
+        self.raw_text_matrix_grid = doc.raw_text_matrix_grid;
+        self.extraction = doc.extraction;
+        self.selected_region = doc.selected_region;
+        self.table_editor = doc.table_editor;
+        self.annotations = doc.annotations;
+        self.selected_annotation = doc.selected_annotation;
+        self.selected_cell = doc.selected_cell;
+        self.selection_start = doc.selection_start;
+        self.selection_end = doc.selection_end;
+        self.visible_page_changed = doc.visible_page_changed;
+        self.is_dragging = false;
+        self.region_drag = None;
+        self.annotation_drag = None;
+        self.scroll_to_page = Some(self.current_page);
+        // Zoom or dark mode may have changed while the tab was in the background
+        self.needs_render = self.pdf_path.is_some();
+    }
+
+    /// Bring another tab's document on screen. Its extraction, if any, kept
+    /// running in the background.
+    fn switch_document(&mut self, index: usize) {
+        if index == self.active_document || index >= self.documents.len() {
+            return;
+        }
+        let parked = self.stash_document();
+        self.documents[self.active_document] = parked;
+        let doc = std::mem::take(&mut self.documents[index]);
+        self.restore_document(doc);
+        self.active_document = index;
+    }
+
+    /// Give the next PDF its own tab, unless the current tab is still empty.
+    fn open_document_tab(&mut self) {
+        if self.pdf_path.is_none() {
+            return;
+        }
+        let parked = self.stash_document();
+        self.documents[self.active_document] = parked;
+        self.documents.push(DocumentState::default());
+        self.active_document = self.documents.len() - 1;
+        self.restore_document(DocumentState::default());
+    }
+
+    /// Close a tab, asking first if its matrix has edits that haven't been saved.
+    fn close_document(&mut self, index: usize) {
+        if index >= self.documents.len() {
+            return;
+        }
+        let (dirty, pdf_path) = if index == self.active_document {
+            (self.matrix_result.matrix_dirty, &self.pdf_path)
+        } else {
+            let doc = &self.documents[index];
+            (doc.matrix_result.matrix_dirty, &doc.pdf_path)
+        };
+        if let (true, Some(path)) = (dirty, pdf_path) {
+            self.pending_discard = Some((path.clone(), DiscardAction::Close));
+            return;
+        }
+        self.discard_document(index);
+    }
+
+    fn discard_document(&mut self, index: usize) {
+        if index != self.active_document {
+            if let Some(task) = self.documents.remove(index).extraction {
+                task.handle.abort();
+            }
+            if index < self.active_document {
+                self.active_document -= 1;
+            }
+            return;
+        }
+
+        self.cancel_extraction();
+        if self.documents.len() == 1 {
+            self.restore_document(DocumentState::default());
+            return;
+        }
+        self.documents.remove(index);
+        let next = index.min(self.documents.len() - 1);
+        let doc = std::mem::take(&mut self.documents[next]);
+        self.restore_document(doc);
+        self.active_document = next;
+    }
+
+    fn show_discard_confirmation(&mut self, ctx: &egui::Context) {
+        let Some((path, action)) = self.pending_discard.clone() else {
+            return;
+        };
+        // Look the tab up by path, since closing other tabs shifts the indices
+        let index = if self.pdf_path.as_ref() == Some(&path) {
+            Some(self.active_document)
+        } else {
+            self.documents
+                .iter()
+                .position(|doc| doc.pdf_path.as_ref() == Some(&path))
+        };
+        let Some(index) = index else {
+            self.pending_discard = None;
+            return;
+        };
+        let name = path
+            .file_name()
+            .map_or_else(|| path.display().to_string(), |n| n.to_string_lossy().to_string());
+
+        let mut open = true;
+        let mut discard = false;
+        let mut cancel = false;
+        egui::Window::new(RichText::new("UNSAVED EDITS").color(palette().yellow).monospace())
+            .id(egui::Id::new("close_confirmation"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .frame(
+                egui::Frame::none()
+                    .fill(palette().bg)
+                    .stroke(Stroke::new(1.0, palette().yellow))
+                    .inner_margin(egui::Margin::same(10.0)),
+            )
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(format!("{} has matrix edits that haven't been saved.", name))
+                        .color(palette().fg)
+                        .monospace(),
+                );
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(
+                            RichText::new(match action {
+                                DiscardAction::Close => "Close without saving",
+                                DiscardAction::Reload => "Reload without saving",
+                            })
+                            .color(palette().error)
+                            .monospace(),
+                        )
+                        .clicked()
+                    {
+                        discard = true;
+                    }
+                    if ui.button(RichText::new("Cancel").monospace()).clicked() {
+                        cancel = true;
+                    }
+                });
+            });
+
+        if discard {
+            self.pending_discard = None;
+            match action {
+                DiscardAction::Close => self.discard_document(index),
+                DiscardAction::Reload => {
+                    self.switch_document(index);
+                    self.matrix_result.matrix_dirty = false;
+                    self.load_pdf(ctx, path);
+                }
+            }
+        } else if cancel || !open {
+            self.pending_discard = None;
+        }
+    }
+
+    fn show_document_tabs(&mut self, ui: &mut egui::Ui) {
+        if self.documents.len() < 2 {
+            return;
+        }
+        let mut switch_to = None;
+        let mut close = None;
+        ui.horizontal(|ui| {
+            for (index, doc) in self.documents.iter().enumerate() {
+                let active = index == self.active_document;
+                let path = if active { &self.pdf_path } else { &doc.pdf_path };
+                let name = path
+                    .as_ref()
+                    .and_then(|p| p.file_name())
+                    .map_or_else(|| "(empty)".to_string(), |n| n.to_string_lossy().to_string());
+                let dirty = if active {
+                    self.matrix_result.matrix_dirty
+                } else {
+                    doc.matrix_result.matrix_dirty
+                };
+                let label = format!(" {}{} ", name, if dirty { "*" } else { "" });
+                let color = if active { palette().highlight } else { palette().dim };
+                let mut response = ui.selectable_label(
+                    active,
+                    RichText::new(label).color(color).monospace().size(11.0),
+                );
+                if let Some(path) = path {
+                    response = response.on_hover_text(path.display().to_string());
+                }
+                if response.clicked() {
+                    switch_to = Some(index);
+                }
+                if ui
+                    .small_button(RichText::new("×").color(palette().dim).monospace())
+                    .on_hover_text("Close tab (Ctrl+W)")
+                    .clicked()
+                {
+                    close = Some(index);
+                }
+                ui.label(RichText::new("│").color(palette().chrome).monospace());
+            }
+        });
+        if let Some(index) = close {
+            self.close_document(index);
+        } else if let Some(index) = switch_to {
+            self.switch_document(index);
+        }
+    }
+
+    /// Pick up a finished extraction, keeping it only if it's for the page on screen.
+    fn poll_extraction(&mut self) {
+        let Some(received) = self.extraction.as_mut().map(|task| task.result.try_recv()) else {
+            return;
+        };
+        let result = match received {
+            Ok(result) => result,
+            Err(mpsc::error::TryRecvError::Empty) => return,
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                Err("Extraction stopped unexpectedly".to_string())
+            }
+        };
+        let Some(task) = self.extraction.take() else {
+            return;
+        };
+        self.matrix_result.is_loading = false;
+
+        if task.page != self.current_page || self.pdf_path.as_ref() != Some(&task.pdf_path) {
+            self.log(&format!(
+                "⏭ Discarded extraction of page {}, no longer current",
+                task.page + 1
+            ));
+            return;
+        }
+
+        match result {
+            Ok(mut character_matrix) => {
+                // Pending table edits have to reach the sidecar before it's read back
+                self.flush_region_edits();
+                let saved = self.pdf_path.as_deref().map(RegionOverrides::load);
+                match saved {
+                    Some(Ok(mut overrides)) => {
+                        if let Some(regions) = overrides.pages.remove(&(self.current_page + 1)) {
+                            character_matrix.text_regions = regions;
+                            self.log("📐 Restored hand-edited regions for this page");
+                        }
+                    }
+                    Some(Err(e)) => self.log(&format!("⚠️ Could not read region edits: {}", e)),
+                    None => {}
+                }
+                self.selected_region = None;
+                self.table_editor = None;
+                self.matrix_result.character_matrix = Some(character_matrix.clone());
+                self.matrix_result.editable_matrix = Some(character_matrix.matrix.clone());
+                self.matrix_result.original_matrix = Some(character_matrix.matrix.clone());
+                self.matrix_result.matrix_dirty = false;
+                self.log(&format!(
+                    "✅ Character matrix extraction completed in {:.1}s",
+                    task.started.elapsed().as_secs_f32()
+                ));
+            }
+            Err(e) => {
+                self.matrix_result.error = Some(e);
+            }
+        }
+    }
+
+    /// Spinner with the running task's stage and elapsed time, plus a cancel button.
+    fn show_extraction_progress(&mut self, ui: &mut egui::Ui, fallback: &str) {
+        let status = match &self.extraction {
+            Some(task) => format!(
+                "Page {}: {}... {}s",
+                task.page + 1,
+                *task.progress.borrow(),
+                task.started.elapsed().as_secs()
+            ),
+            None => fallback.to_string(),
+        };
+        let mut cancel = false;
+        ui.vertical_centered(|ui| {
+            ui.add_space(ui.available_height() / 3.0);
+            ui.spinner();
+            ui.label(RichText::new(status).color(palette().fg).monospace());
+            if self.extraction.is_some()
+                && ui
+                    .button(
+                        RichText::new("[Esc] Cancel")
+                            .color(palette().fg)
+                            .monospace()
+                            .size(12.0),
+                    )
+                    .clicked()
+            {
+                cancel = true;
+            }
+        });
+        cancel |= self.extraction.is_some() && ui.input(|i| i.key_pressed(egui::Key::Escape));
+        if cancel {
+            self.cancel_extraction();
+        }
+        ui.ctx().request_repaint_after(std::time::Duration::from_millis(250));
+    }
+
+    async fn process_pdf_async(
         pdf_path: PathBuf,
         page_index: usize,
+        progress: tokio::sync::watch::Sender<&'static str>,
     ) -> Result<CharacterMatrix, String> {
-        let result = tokio::task::spawn_blocking(move || {
-            tracing::info!(
-                "Starting async PDF processing: {} (page {})",
-                pdf_path.display(),
-                page_index + 1
-            );
+        tracing::info!(
+            "Starting async PDF processing: {} (page {})",
+            pdf_path.display(),
+            page_index + 1
+        );
+
+        let start_time = std::time::Instant::now();
+        let timeout = std::time::Duration::from_secs(60);
+
+        // PDFium can't be interrupted; on cancel its result is just dropped
+        let _ = progress.send("Reading text with PDFium");
+        let task_path = pdf_path.clone();
+        let pdfium_result = tokio::task::spawn_blocking(move || {
+            let engine = CharacterMatrixEngine::new();
+            engine
+                .process_pdf_page(&task_path, Some(page_index))
+                .map_err(|e| format!("PDFium extraction failed: {}", e))
+        })
+        .await
+        .unwrap_or_else(|join_err| Err(format!("PDF processing task failed: {}", join_err)));
+        let pdfium_err = match pdfium_result {
+            Ok(matrix) => {
+                tracing::info!("PDFium extraction successful in {:?}", start_time.elapsed());
+                return Ok(matrix);
+            }
+            Err(e) => e,
+        };
+        tracing::warn!("{}, trying mutool", pdfium_err);
 
-            let start_time = std::time::Instant::now();
-            let timeout = std::time::Duration::from_secs(60);
+        if start_time.elapsed() > timeout {
+            return Err("PDF processing timeout - file too complex".to_string());
+        }
 
-            let rt = tokio::runtime::Handle::current();
+        // mutool is optional; it only helps when PDFium can't be loaded or read the file
+        let _ = progress.send("Falling back to mutool");
+        Self::extract_simple_text_matrix(&pdf_path, page_index)
+            .await
+            .map_err(|e| format!("{} ({})", e, pdfium_err))
+    }
 
-            match rt.block_on(Self::extract_simple_text_matrix(&pdf_path, page_index)) {
-                Ok(matrix) => {
-                    tracing::info!(
-                        "Simple text extraction successful in {:?}",
-                        start_time.elapsed()
-                    );
-                    Ok(matrix)
+    async fn extract_simple_text_matrix(
+        pdf_path: &PathBuf,
+        page_index: usize,
+    ) -> Result<CharacterMatrix, String> {
+        let output = tokio::process::Command::new("mutool")
+            .arg("draw")
+            .arg("-F")
+            .arg("text")
+            .arg(pdf_path)
+            .arg((page_index + 1).to_string())
+            .kill_on_drop(true) // Cancelling the task kills mutool too
+            .output()
+            .await
+            .map_err(|e| format!("Failed to run mutool: {}", e))?;
+
+        if !output.status.success() {
+            return Err("Mutool extraction failed".to_string());
+        }
+
+        let text = String::from_utf8_lossy(&output.stdout);
+        let lines: Vec<&str> = text.lines().collect();
+        let max_width = lines.iter().map(|line| line.len()).max().unwrap_or(80);
+        let height = lines.len().max(25);
+
+        let mut matrix = vec![vec![' '; max_width]; height];
+
+        for (y, line) in lines.iter().enumerate() {
+            if y < height {
+                for (x, ch) in line.chars().enumerate() {
+                    if x < max_width {
+                        matrix[y][x] = ch;
+                    }
+                }
+            }
+        }
+
+        Ok(CharacterMatrix {
+            width: max_width,
+            height,
+            matrix,
+            text_regions: Vec::new(),
+            original_text: lines.iter().map(|s| s.to_string()).collect(),
+            char_width: 8.0,
+            char_height: 12.0,
+        })
+    }
+
+    fn save_edited_matrix(&mut self) {
+        if let Some(editable_matrix) = &self.matrix_result.editable_matrix {
+            if let Some(pdf_path) = &self.pdf_path {
+                let output_path = pdf_path.with_extension("matrix.txt");
+
+                let mut content = String::new();
+                for row in editable_matrix {
+                    for ch in row {
+                        content.push(*ch);
+                    }
+                    content.push('\n');
+                }
+
+                match std::fs::write(&output_path, content) {
+                    Ok(_) => {
+                        self.log(&format!(
+                            "✅ Saved edited matrix to: {}",
+                            output_path.display()
+                        ));
+                        self.matrix_result.matrix_dirty = false;
+                    }
+                    Err(e) => {
+                        self.log(&format!("❌ Failed to save matrix: {}", e));
+                    }
                 }
-                Err(simple_err) => {
-                    tracing::warn!("Simple extraction failed: {}, trying PDFium", simple_err);
+            }
+        }
+    }
+
+    fn handle_region_editing(&mut self, ui: &mut egui::Ui, image_rect: Rect) {
+        let response = ui.interact(
+            image_rect,
+            ui.id().with("region_edit"),
+            Sense::click_and_drag(),
+        );
+        let delete_pressed = self.focused_pane == FocusedPane::PdfView
+            && ui.input(|i| i.key_pressed(egui::Key::Delete) || i.key_pressed(egui::Key::Backspace));
+
+        let Some(char_matrix) = &mut self.matrix_result.character_matrix else {
+            return;
+        };
+        if char_matrix.width == 0 || char_matrix.height == 0 {
+            return;
+        }
+        let (width, height) = (char_matrix.width, char_matrix.height);
+        let cell_at = |pos: egui::Pos2| {
+            let local = pos - image_rect.min;
+            (
+                ((local.x / image_rect.width() * width as f32).max(0.0) as usize).min(width - 1),
+                ((local.y / image_rect.height() * height as f32).max(0.0) as usize).min(height - 1),
+            )
+        };
+        let region_at = |regions: &[TextRegion], cell: (usize, usize)| {
+            // Topmost (last drawn) region wins where boxes overlap
+            regions.iter().rposition(|r| r.bbox.contains(cell.0, cell.1))
+        };
+
+        if response.clicked() {
+            if let Some(pos) = response.interact_pointer_pos() {
+                self.selected_region = region_at(&char_matrix.text_regions, cell_at(pos));
+            }
+        }
+
+        if response.drag_started() {
+            if let Some(pos) = response.interact_pointer_pos() {
+                let cell = cell_at(pos);
+                let on_handle = self
+                    .selected_region
+                    .and_then(|i| char_matrix.text_regions.get(i))
+                    .is_some_and(|r| {
+                        cell == (r.bbox.x + r.bbox.width - 1, r.bbox.y + r.bbox.height - 1)
+                    });
 
-                    if start_time.elapsed() > timeout {
-                        return Err("PDF processing timeout - file too complex".to_string());
+                self.region_drag = if on_handle {
+                    self.selected_region.map(|index| RegionDrag::Resize { index })
+                } else if let Some(index) = region_at(&char_matrix.text_regions, cell) {
+                    self.selected_region = Some(index);
+                    Some(RegionDrag::Move {
+                        index,
+                        grab: cell,
+                        original: char_matrix.text_regions[index].bbox.clone(),
+                    })
+                } else {
+                    self.selected_region = None;
+                    Some(RegionDrag::Draw {
+                        start: cell,
+                        end: cell,
+                    })
+                };
+            }
+        }
+
+        if response.dragged() {
+            if let (Some(pos), Some(drag)) = (response.interact_pointer_pos(), &mut self.region_drag)
+            {
+                let cell = cell_at(pos);
+                match drag {
+                    RegionDrag::Draw { end, .. } => *end = cell,
+                    RegionDrag::Move {
+                        index,
+                        grab,
+                        original,
+                    } => {
+                        let bbox = &mut char_matrix.text_regions[*index].bbox;
+                        let dx = cell.0 as isize - grab.0 as isize;
+                        let dy = cell.1 as isize - grab.1 as isize;
+                        bbox.x = (original.x as isize + dx)
+                            .clamp(0, (width - original.width.min(width)) as isize)
+                            as usize;
+                        bbox.y = (original.y as isize + dy)
+                            .clamp(0, (height - original.height.min(height)) as isize)
+                            as usize;
                     }
+                    RegionDrag::Resize { index } => {
+                        let bbox = &mut char_matrix.text_regions[*index].bbox;
+                        bbox.width = cell.0.saturating_sub(bbox.x) + 1;
+                        bbox.height = cell.1.saturating_sub(bbox.y) + 1;
+                    }
+                }
+            }
+        }
+
+        let mut edited = false;
+        if response.drag_released() {
+            edited = self.region_drag.is_some();
+            let matrix = self
+                .matrix_result
+                .editable_matrix
+                .as_ref()
+                .unwrap_or(&char_matrix.matrix);
+            match self.region_drag.take() {
+                Some(RegionDrag::Draw { start, end }) => {
+                    let bbox = CharBBox::from_corners(start, end);
+                    let region_id = char_matrix
+                        .text_regions
+                        .iter()
+                        .map(|r| r.region_id + 1)
+                        .max()
+                        .unwrap_or(0);
+                    char_matrix.text_regions.push(TextRegion {
+                        text_content: bbox.text_in(matrix),
+                        bbox,
+                        confidence: 1.0, // Drawn by hand
+                        region_id,
+                        block_type: BlockType::default(),
+                        table: None,
+                    });
+                    self.selected_region = Some(char_matrix.text_regions.len() - 1);
+                }
+                Some(RegionDrag::Move { index, .. }) | Some(RegionDrag::Resize { index }) => {
+                    let region = &mut char_matrix.text_regions[index];
+                    region.text_content = region.bbox.text_in(matrix);
+                }
+                None => {}
+            }
+        }
+
+        if delete_pressed && self.region_drag.is_none() {
+            if let Some(index) = self.selected_region.take() {
+                if index < char_matrix.text_regions.len() {
+                    char_matrix.text_regions.remove(index);
+                    edited = true;
+                }
+            }
+        }
+
+        if edited {
+            self.save_region_overrides();
+        }
+    }
 
-                    let engine = CharacterMatrixEngine::new();
-                    engine
-                        .process_pdf_page(&pdf_path, Some(page_index))
-                        .map_err(|e| format!("Ferrules processing failed: {}", e))
+    fn handle_annotation_drawing(&mut self, ui: &mut egui::Ui, image_rect: Rect) {
+        let response = ui.interact(image_rect, ui.id().with("annotate"), Sense::click_and_drag());
+        let Some(char_matrix) = &self.matrix_result.character_matrix else {
+            return;
+        };
+        if char_matrix.width == 0 || char_matrix.height == 0 {
+            return;
+        }
+        let (width, height) = (char_matrix.width, char_matrix.height);
+        let cell_at = |pos: egui::Pos2| {
+            let local = pos - image_rect.min;
+            (
+                ((local.x / image_rect.width() * width as f32).max(0.0) as usize).min(width - 1),
+                ((local.y / image_rect.height() * height as f32).max(0.0) as usize).min(height - 1),
+            )
+        };
+
+        if response.clicked() {
+            if let Some(pos) = response.interact_pointer_pos() {
+                let cell = cell_at(pos);
+                self.selected_annotation = self
+                    .annotations
+                    .pages
+                    .get(&(self.current_page + 1))
+                    .and_then(|notes| notes.iter().rposition(|n| n.bbox.contains(cell.0, cell.1)));
+            }
+        }
+        if response.drag_started() {
+            if let Some(pos) = response.interact_pointer_pos() {
+                let cell = cell_at(pos);
+                self.annotation_drag = Some((cell, cell));
+            }
+        }
+        if response.dragged() {
+            if let (Some(pos), Some((_, end))) =
+                (response.interact_pointer_pos(), &mut self.annotation_drag)
+            {
+                *end = cell_at(pos);
+            }
+        }
+        if response.drag_released() {
+            if let Some((start, end)) = self.annotation_drag.take() {
+                self.add_annotation(CharBBox::from_corners(start, end));
+            }
+        }
+    }
+
+    fn add_annotation(&mut self, bbox: CharBBox) {
+        let Some(char_matrix) = &self.matrix_result.character_matrix else {
+            return;
+        };
+        let matrix = self
+            .matrix_result
+            .editable_matrix
+            .as_ref()
+            .unwrap_or(&char_matrix.matrix);
+        let note = Annotation {
+            quote: bbox.text_in(matrix),
+            bbox,
+            comment: String::new(),
+            author: std::env::var("USER").unwrap_or_default(),
+            created: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+        };
+        let notes = self.annotations.pages.entry(self.current_page + 1).or_default();
+        notes.push(note);
+        self.selected_annotation = Some(notes.len() - 1);
+        self.save_annotations();
+    }
+
+    fn save_annotations(&mut self) {
+        self.notes_edited = None;
+        let Some(pdf_path) = &self.pdf_path else {
+            return;
+        };
+        if let Err(e) = self.annotations.save(pdf_path) {
+            self.log(&format!("❌ Failed to save notes: {}", e));
+        }
+    }
+
+    /// Write out comment edits that are still waiting on EDIT_SAVE_DELAY.
+    fn flush_annotations(&mut self) {
+        if self.notes_edited.is_some() {
+            self.save_annotations();
+        }
+    }
+
+    fn draw_annotations(&self, ui: &mut egui::Ui, image_rect: Rect) {
+        let Some(char_matrix) = &self.matrix_result.character_matrix else {
+            return;
+        };
+        if char_matrix.width == 0 || char_matrix.height == 0 {
+            return;
+        }
+        let cell = egui::vec2(
+            image_rect.width() / char_matrix.width as f32,
+            image_rect.height() / char_matrix.height as f32,
+        );
+        let to_screen = |bbox: &CharBBox| {
+            Rect::from_min_size(
+                image_rect.min + egui::vec2(bbox.x as f32 * cell.x, bbox.y as f32 * cell.y),
+                egui::vec2(bbox.width as f32 * cell.x, bbox.height as f32 * cell.y),
+            )
+        };
+
+        let painter = ui.painter();
+        let notes = self.annotations.pages.get(&(self.current_page + 1));
+        for (index, note) in notes.into_iter().flatten().enumerate() {
+            let rect = to_screen(&note.bbox);
+            painter.rect_filled(rect, 0.0, palette().yellow.gamma_multiply(0.25));
+            if self.selected_annotation == Some(index) {
+                painter.rect_stroke(rect, 0.0, Stroke::new(2.0, palette().yellow));
+            }
+            if !note.comment.trim().is_empty() {
+                painter.text(
+                    rect.right_top(),
+                    Align2::RIGHT_BOTTOM,
+                    "✎",
+                    FontId::monospace(11.0),
+                    palette().yellow,
+                );
+            }
+        }
+        if let Some((start, end)) = self.annotation_drag {
+            let rect = to_screen(&CharBBox::from_corners(start, end));
+            painter.rect_stroke(rect, 0.0, Stroke::new(1.5, palette().yellow));
+        }
+    }
+
+    fn show_annotation_panel(&mut self, ctx: &egui::Context) {
+        if !self.annotate_mode || self.pdf_path.is_none() {
+            return;
+        }
+        let page = self.current_page + 1;
+        let matrix_selection = self
+            .raw_text_matrix_grid
+            .as_ref()
+            .and_then(|grid| Some((grid.selection.start?, grid.selection.end?)));
+
+        let mut open = true;
+        let mut changed = false;
+        let mut edited = false;
+        let mut delete = None;
+        let mut from_selection = false;
+        let selected = &mut self.selected_annotation;
+        let mut no_notes = Vec::new();
+        let notes = self.annotations.pages.get_mut(&page).unwrap_or(&mut no_notes);
+        egui::Window::new(
+            RichText::new(format!("NOTES p.{}", page))
+                .color(palette().highlight)
+                .monospace(),
+        )
+        .id(egui::Id::new("annotation_panel"))
+        .open(&mut open)
+        .collapsible(false)
+        .default_width(300.0)
+        .anchor(Align2::LEFT_BOTTOM, egui::vec2(12.0, -12.0))
+        .frame(
+            egui::Frame::none()
+                .fill(palette().bg)
+                .stroke(Stroke::new(1.0, palette().highlight))
+                .inner_margin(egui::Margin::same(8.0)),
+        )
+        .show(ctx, |ui| {
+            ui.label(
+                RichText::new("Drag on the page to highlight")
+                    .color(palette().dim)
+                    .monospace()
+                    .size(10.0),
+            );
+            if ui
+                .add_enabled(
+                    matrix_selection.is_some(),
+                    egui::Button::new(RichText::new("+ From matrix selection").monospace()),
+                )
+                .clicked()
+            {
+                from_selection = true;
+            }
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .max_height(320.0)
+                .show(ui, |ui| {
+                    for (index, note) in notes.iter_mut().enumerate() {
+                        let quote: String = note
+                            .quote
+                            .split_whitespace()
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                            .chars()
+                            .take(40)
+                            .collect();
+                        let label = format!("N{} \"{}\"", index + 1, quote);
+                        if ui
+                            .selectable_label(
+                                *selected == Some(index),
+                                RichText::new(label).monospace().size(10.0),
+                            )
+                            .clicked()
+                        {
+                            *selected = Some(index);
+                        }
+                        if *selected == Some(index) {
+                            let response = ui.add(
+                                egui::TextEdit::multiline(&mut note.comment)
+                                    .hint_text("Comment")
+                                    .desired_rows(3)
+                                    .desired_width(f32::INFINITY),
+                            );
+                            edited |= response.changed();
+                            ui.horizontal(|ui| {
+                                ui.label(
+                                    RichText::new(format!("{} {}", note.author, note.created))
+                                        .color(palette().dim)
+                                        .monospace()
+                                        .size(9.0),
+                                );
+                                if ui.small_button("Delete").clicked() {
+                                    delete = Some(index);
+                                }
+                            });
+                        }
+                    }
+                });
+        });
+        self.annotate_mode = open;
+
+        if let Some(index) = delete {
+            if let Some(notes) = self.annotations.pages.get_mut(&page) {
+                notes.remove(index);
+            }
+            self.selected_annotation = None;
+            changed = true;
+        }
+        if changed {
+            self.save_annotations();
+        } else if edited {
+            self.notes_edited = Some(Instant::now());
+        }
+        if let (true, Some((start, end))) = (from_selection, matrix_selection) {
+            // Matrix selections are (row, col); boxes take (x, y) corners
+            self.add_annotation(CharBBox::from_corners((start.1, start.0), (end.1, end.0)));
+        }
+    }
+
+    fn save_region_overrides(&mut self) {
+        self.flush_region_edits();
+        let Some(char_matrix) = &self.matrix_result.character_matrix else {
+            return;
+        };
+        let regions = char_matrix.text_regions.clone();
+        self.write_region_overrides(self.current_page, regions);
+    }
+
+    /// Keep a copy of the page's regions to write once typing pauses, so a
+    /// page change in the meantime can't lose or misfile them.
+    fn queue_region_save(&mut self) {
+        if let Some(char_matrix) = &self.matrix_result.character_matrix {
+            let regions = char_matrix.text_regions.clone();
+            self.region_edits = Some((Instant::now(), self.current_page, regions));
+        }
+    }
+
+    fn flush_region_edits(&mut self) {
+        if let Some((_, page, regions)) = self.region_edits.take() {
+            self.write_region_overrides(page, regions);
+        }
+    }
+
+    fn write_region_overrides(&mut self, page: usize, regions: Vec<TextRegion>) {
+        let Some(pdf_path) = &self.pdf_path else {
+            return;
+        };
+        let result = RegionOverrides::load(pdf_path).and_then(|mut overrides| {
+            overrides.pages.insert(page + 1, regions);
+            overrides.save(pdf_path)
+        });
+        if let Err(e) = result {
+            self.log(&format!("❌ Failed to save region edits: {}", e));
+        }
+    }
+
+    fn show_block_list(&mut self, ctx: &egui::Context) {
+        if !self.show_block_list {
+            return;
+        }
+        let Some(char_matrix) = &self.matrix_result.character_matrix else {
+            return;
+        };
+
+        let mut open = true;
+        let mut clicked = None;
+        let selected = self.selected_region;
+        egui::Window::new(
+            RichText::new(format!("BLOCKS ({})", char_matrix.text_regions.len()))
+                .color(palette().highlight)
+                .monospace(),
+        )
+        .id(egui::Id::new("block_list"))
+        .open(&mut open)
+        .collapsible(false)
+        .default_width(340.0)
+        .anchor(Align2::LEFT_TOP, egui::vec2(12.0, 48.0))
+        .frame(
+            egui::Frame::none()
+                .fill(palette().bg)
+                .stroke(Stroke::new(1.0, palette().highlight))
+                .inner_margin(egui::Margin::same(8.0)),
+        )
+        .show(ctx, |ui| {
+            if char_matrix.text_regions.is_empty() {
+                ui.label(
+                    RichText::new("No blocks on this page")
+                        .color(palette().dim)
+                        .monospace()
+                        .size(10.0),
+                );
+            }
+            egui::ScrollArea::vertical()
+                .max_height(420.0)
+                .show(ui, |ui| {
+                    for (index, region) in char_matrix.text_regions.iter().enumerate() {
+                        let preview: String = region
+                            .text_content
+                            .split_whitespace()
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                            .chars()
+                            .take(36)
+                            .collect();
+                        let label = format!(
+                            "R{:<3} {:<9} {:>3.0}% {}",
+                            region.region_id + 1,
+                            region.block_type.label(),
+                            region.confidence * 100.0,
+                            preview
+                        );
+                        if ui
+                            .selectable_label(
+                                selected == Some(index),
+                                RichText::new(label).monospace().size(10.0),
+                            )
+                            .clicked()
+                        {
+                            clicked = Some(index);
+                        }
+                    }
+                });
+        });
+        self.show_block_list = open;
+
+        if let Some(index) = clicked {
+            self.focus_region(index);
+        }
+    }
+
+    /// Zoom the page onto a region and select its cells in the matrix.
+    fn focus_region(&mut self, index: usize) {
+        let Some(char_matrix) = &self.matrix_result.character_matrix else {
+            return;
+        };
+        let Some(region) = char_matrix.text_regions.get(index) else {
+            return;
+        };
+        let bbox = region.bbox.clone();
+
+        // Zoom 1.0 fits the whole page, so fit the block with a margin around it
+        let width_frac = bbox.width.max(1) as f32 / char_matrix.width.max(1) as f32;
+        let height_frac = bbox.height.max(1) as f32 / char_matrix.height.max(1) as f32;
+        let zoom = (0.8 / width_frac.max(height_frac)).clamp(0.5, 3.0);
+        if (zoom - self.zoom_level).abs() > 0.01 {
+            self.zoom_level = zoom;
+            self.needs_render = true;
+        }
+
+        self.selected_region = Some(index);
+        self.show_bounding_boxes = true;
+        self.scroll_to_region = Some(index);
+        if self.continuous_scroll {
+            // Bring the page into view; the region is scrolled to once it's laid out
+            self.scroll_to_page = Some(self.current_page);
+        }
+
+        if let Some(grid) = &mut self.raw_text_matrix_grid {
+            grid.selection.start = Some((bbox.y, bbox.x));
+            grid.selection.end = Some((
+                bbox.y + bbox.height.saturating_sub(1),
+                bbox.x + bbox.width.saturating_sub(1),
+            ));
+            grid.cursor_pos = None;
+            grid.scroll_to = Some((bbox.y, bbox.x));
+        }
+        self.active_tab = ExtractionTab::RawText;
+    }
+
+    /// Where a region's box falls on the displayed page image.
+    fn region_screen_rect(&self, index: usize, image_rect: Rect) -> Option<Rect> {
+        let char_matrix = self.matrix_result.character_matrix.as_ref()?;
+        let bbox = &char_matrix.text_regions.get(index)?.bbox;
+        let cell = egui::vec2(
+            image_rect.width() / char_matrix.width.max(1) as f32,
+            image_rect.height() / char_matrix.height.max(1) as f32,
+        );
+        Some(Rect::from_min_size(
+            image_rect.min + egui::vec2(bbox.x as f32 * cell.x, bbox.y as f32 * cell.y),
+            egui::vec2(bbox.width as f32 * cell.x, bbox.height as f32 * cell.y),
+        ))
+    }
+
+    fn show_region_inspector(&mut self, ctx: &egui::Context) {
+        if !self.region_edit_mode {
+            return;
+        }
+        let Some(index) = self.selected_region else {
+            return;
+        };
+        let Some(region) = self
+            .matrix_result
+            .character_matrix
+            .as_mut()
+            .and_then(|m| m.text_regions.get_mut(index))
+        else {
+            return;
+        };
+
+        let mut changed = false;
+        let mut open_table = false;
+        egui::Window::new(
+            RichText::new(format!("REGION R{}", region.region_id + 1))
+                .color(palette().highlight)
+                .monospace(),
+        )
+        .id(egui::Id::new("region_inspector"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(Align2::LEFT_BOTTOM, egui::vec2(12.0, -12.0))
+        .frame(
+            egui::Frame::none()
+                .fill(palette().bg)
+                .stroke(Stroke::new(1.0, palette().highlight))
+                .inner_margin(egui::Margin::same(8.0)),
+        )
+        .show(ctx, |ui| {
+            egui::ComboBox::from_label(RichText::new("Block type").color(palette().fg).monospace())
+                .selected_text(region.block_type.label())
+                .show_ui(ui, |ui| {
+                    for block_type in BlockType::ALL {
+                        changed |= ui
+                            .selectable_value(&mut region.block_type, block_type, block_type.label())
+                            .changed();
+                    }
+                });
+            ui.label(
+                RichText::new(format!(
+                    "({},{}) {}x{}  conf:{:.2}",
+                    region.bbox.x,
+                    region.bbox.y,
+                    region.bbox.width,
+                    region.bbox.height,
+                    region.confidence
+                ))
+                .color(palette().dim)
+                .monospace()
+                .size(10.0),
+            );
+            ui.label(
+                RichText::new(region.text_content.chars().take(120).collect::<String>())
+                    .color(palette().fg)
+                    .monospace()
+                    .size(10.0),
+            );
+            if region.block_type == BlockType::Table
+                && ui
+                    .button(RichText::new("[T] Edit table").color(palette().fg).monospace())
+                    .clicked()
+            {
+                open_table = true;
+            }
+        });
+
+        if open_table {
+            self.table_editor = Some(index);
+        }
+        if changed {
+            self.save_region_overrides();
+        }
+    }
+
+    fn ensure_ferrules_output(&mut self, pdf_path: &PathBuf) {
+        if self.ferrules_output_cache.is_some() {
+            return;
+        }
+        let page = CharacterMatrixEngine::ferrules_supports_page().then_some(self.current_page);
+        self.log(&format!("🔄 Running Ferrules for page {}...", self.current_page + 1));
+        match self.matrix_engine.run_ferrules_integration_test(pdf_path, page) {
+            Ok(console_output) => {
+                let heading = match page {
+                    Some(page) => format!("📄 Page {}/{}", page + 1, self.total_pages),
+                    None => "📄 This Ferrules build can't pick a page".to_string(),
+                };
+                self.ferrules_output_cache = Some(format!("{}\n{}", heading, console_output));
+                self.ferrules_matrix_grid = Some(MatrixGrid::new(&console_output));
+                self.ferrules_source = page.map(|page| (pdf_path.clone(), page));
+                self.log("✅ Ferrules analysis complete");
+            }
+            Err(e) => {
+                self.ferrules_output_cache = Some(format!("❌ Terminal command failed: {}", e));
+                self.log(&format!("❌ Ferrules failed: {}", e));
+            }
+        }
+    }
+
+    /// Extract the page with PDFium and, unless its matrix is already known,
+    /// with Smart Layout. Blocks, so it runs off the UI thread.
+    fn run_engine_comparison(
+        pdf_path: &PathBuf,
+        page: usize,
+        smart: Option<Vec<Vec<char>>>,
+    ) -> Result<(Vec<Vec<char>>, Vec<Vec<char>>), String> {
+        let engine = CharacterMatrixEngine::new();
+        let pdfium = engine
+            .process_pdf_page(pdf_path, Some(page))
+            .map_err(|e| format!("PDFium extraction failed: {}", e))?;
+        let smart = match smart {
+            Some(matrix) => matrix,
+            None if !CharacterMatrixEngine::ferrules_supports_page() => {
+                return Err("This Ferrules build has no --page option, so its output \
+                            can't be matched to a page for comparison"
+                    .to_string())
+            }
+            None => {
+                let output = engine
+                    .run_ferrules_integration_test(pdf_path, Some(page))
+                    .map_err(|e| format!("Smart Layout failed: {}", e))?;
+                MatrixGrid::new(&output).matrix
+            }
+        };
+        Ok((pdfium.matrix, smart))
+    }
+
+    fn start_engine_comparison(&mut self, ctx: &egui::Context, pdf_path: PathBuf) {
+        if let Some(task) = self.comparison_task.take() {
+            task.handle.abort();
+        }
+        let page = self.current_page;
+        // Reuse the Smart Layout tab's matrix only if it was run on this page
+        let smart = match (&self.ferrules_matrix_grid, &self.ferrules_source) {
+            (Some(grid), Some((path, source_page)))
+                if *path == pdf_path && *source_page == page =>
+            {
+                Some(grid.matrix.clone())
+            }
+            _ => None,
+        };
+        self.log(&format!("🔄 Comparing engines on page {}...", page + 1));
+
+        let (tx, rx) = mpsc::channel(1);
+        let ctx = ctx.clone();
+        let task_path = pdf_path.clone();
+        let handle = self.runtime.spawn(async move {
+            let result = tokio::task::spawn_blocking(move || {
+                Self::run_engine_comparison(&task_path, page, smart)
+            })
+            .await
+            .unwrap_or_else(|e| Err(format!("Comparison task failed: {}", e)));
+
+            if let Err(e) = tx.send(result).await {
+                tracing::error!("Failed to send comparison result: {}", e);
+            }
+
+            ctx.request_repaint();
+        });
+
+        self.comparison_task = Some(ComparisonTask {
+            pdf_path,
+            page,
+            handle,
+            result: rx,
+        });
+    }
+
+    /// Pick up a finished comparison and diff the two matrices.
+    fn poll_comparison(&mut self) {
+        let Some(received) = self.comparison_task.as_mut().map(|task| task.result.try_recv())
+        else {
+            return;
+        };
+        let result = match received {
+            Ok(result) => result,
+            Err(mpsc::error::TryRecvError::Empty) => return,
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                Err("Comparison stopped unexpectedly".to_string())
+            }
+        };
+        let Some(task) = self.comparison_task.take() else {
+            return;
+        };
+
+        let mut changed_cells = 0;
+        let grids = result.map(|(pdfium, smart)| {
+            let diff = cell_diff(&pdfium, &smart);
+            changed_cells = diff.iter().flatten().filter(|&&changed| changed).count();
+
+            let grid = |matrix: Vec<Vec<char>>, tint: Color32| {
+                let mut grid = MatrixGrid::new("");
+                grid.matrix = matrix;
+                grid.has_focus = false;
+                grid.cell_tints = diff
+                    .iter()
+                    .map(|row| row.iter().map(|&changed| changed.then_some(tint)).collect())
+                    .collect();
+                grid
+            };
+            (
+                grid(pdfium, palette().error.gamma_multiply(0.35)),
+                grid(smart, palette().green.gamma_multiply(0.35)),
+            )
+        });
+
+        self.comparison = Some(EngineComparison {
+            pdf_path: task.pdf_path,
+            page: task.page,
+            grids,
+            changed_cells,
+        });
+    }
+
+    fn show_engine_comparison(&mut self, ui: &mut egui::Ui) {
+        let Some(pdf_path) = self.pdf_path.clone() else {
+            ui.label(RichText::new("No PDF loaded").color(palette().dim).monospace());
+            return;
+        };
+        self.poll_comparison();
+        let page = self.current_page;
+        let stale = self
+            .comparison
+            .as_ref()
+            .is_none_or(|c| c.pdf_path != pdf_path || c.page != page);
+        if stale {
+            let running = self
+                .comparison_task
+                .as_ref()
+                .is_some_and(|task| task.pdf_path == pdf_path && task.page == page);
+            if !running {
+                self.start_engine_comparison(ui.ctx(), pdf_path);
+            }
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(
+                    RichText::new(format!("Comparing engines on page {}...", page + 1))
+                        .color(palette().dim)
+                        .monospace(),
+                );
+            });
+            return;
+        }
+        let Some(comparison) = &mut self.comparison else {
+            return;
+        };
+
+        let mut rerun = false;
+        match &mut comparison.grids {
+            Ok((pdfium, smart)) => {
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new(format!(
+                            "PDFium only │ Smart Layout — {} cells differ",
+                            comparison.changed_cells
+                        ))
+                        .color(palette().dim)
+                        .monospace()
+                        .size(10.0),
+                    );
+                    rerun = accessible(ui.small_button("↻"), "Run the comparison again")
+                        .on_hover_text("Run both again")
+                        .clicked();
+                });
+                // Both grids share the surrounding scroll area, so they scroll together
+                ui.horizontal_top(|ui| {
+                    for grid in [pdfium, smart] {
+                        grid.font_size = self.matrix_font_size;
+                        egui::Frame::none().fill(palette().bg).show(ui, |ui| grid.show(ui));
+                        ui.separator();
+                    }
+                });
+            }
+            Err(e) => {
+                ui.label(RichText::new(format!("❌ {}", e)).color(palette().error).monospace());
+                rerun = ui.button("Retry").clicked();
+            }
+        }
+        if rerun {
+            self.comparison = None;
+            if let Some(task) = self.comparison_task.take() {
+                task.handle.abort();
+            }
+            self.ferrules_output_cache = None;
+            self.ferrules_matrix_grid = None;
+        }
+    }
+
+    fn active_matrix_grid(&mut self) -> Option<&mut MatrixGrid> {
+        match self.active_tab {
+            ExtractionTab::RawText => self.raw_text_matrix_grid.as_mut(),
+            ExtractionTab::SmartLayout => self.ferrules_matrix_grid.as_mut(),
+            ExtractionTab::Compare => None,
+        }
+    }
+
+    fn show_search_panel(&mut self, ctx: &egui::Context) {
+        for grid in [&mut self.raw_text_matrix_grid, &mut self.ferrules_matrix_grid]
+            .into_iter()
+            .flatten()
+        {
+            grid.highlights.clear();
+            grid.current_highlight = None;
+        }
+        if !self.search.open {
+            return;
+        }
+
+        let query = self.search.query.clone();
+        let case_sensitive = self.search.case_sensitive;
+        let mut matches = self
+            .active_matrix_grid()
+            .map(|grid| find_matches(&grid.matrix, &query, case_sensitive))
+            .unwrap_or_default();
+        let region_hits: Vec<(usize, String)> = self
+            .matrix_result
+            .character_matrix
+            .iter()
+            .flat_map(|m| m.text_regions.iter().enumerate())
+            .filter(|(_, region)| {
+                let text: Vec<char> = region.text_content.chars().collect();
+                !find_matches(&[text], &query, case_sensitive).is_empty()
+            })
+            .map(|(i, region)| {
+                (
+                    i,
+                    format!(
+                        "R{} [{}] {}",
+                        region.region_id + 1,
+                        region.block_type.label(),
+                        region.text_content.chars().take(40).collect::<String>()
+                    ),
+                )
+            })
+            .collect();
+
+        let mut open = true;
+        let mut action = self.search.pending.take();
+        let search = &mut self.search;
+        egui::Window::new(RichText::new("FIND").color(palette().highlight).monospace())
+            .id(egui::Id::new("search_panel"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::RIGHT_TOP, egui::vec2(-12.0, 48.0))
+            .frame(
+                egui::Frame::none()
+                    .fill(palette().bg)
+                    .stroke(Stroke::new(1.0, palette().highlight))
+                    .inner_margin(egui::Margin::same(8.0)),
+            )
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut search.query)
+                            .font(egui::TextStyle::Monospace)
+                            .hint_text("Find")
+                            .desired_width(180.0),
+                    );
+                    if search.focus_query {
+                        search.focus_query = false;
+                        response.request_focus();
+                    }
+                    if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        action = Some(if ui.input(|i| i.modifiers.shift) {
+                            SearchAction::Previous
+                        } else {
+                            SearchAction::Next
+                        });
+                        response.request_focus();
+                    }
+                    if ui.small_button("◀").clicked() {
+                        action = Some(SearchAction::Previous);
+                    }
+                    if ui.small_button("▶").clicked() {
+                        action = Some(SearchAction::Next);
+                    }
+                    let status = if matches.is_empty() {
+                        "no matches".to_string()
+                    } else {
+                        format!("{}/{}", search.current.min(matches.len() - 1) + 1, matches.len())
+                    };
+                    ui.label(RichText::new(status).color(palette().dim).monospace().size(10.0));
+                });
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut search.replacement)
+                            .font(egui::TextStyle::Monospace)
+                            .hint_text("Replace")
+                            .desired_width(180.0),
+                    );
+                    if ui.small_button("Replace").clicked() {
+                        action = Some(SearchAction::Replace);
+                    }
+                    if ui.small_button("All").clicked() {
+                        action = Some(SearchAction::ReplaceAll);
+                    }
+                });
+                ui.checkbox(&mut search.case_sensitive, "Match case");
+
+                if !region_hits.is_empty() {
+                    ui.separator();
+                    ui.label(RichText::new("Regions").color(palette().dim).monospace().size(10.0));
+                    for (index, label) in &region_hits {
+                        if ui
+                            .selectable_label(false, RichText::new(label).monospace().size(10.0))
+                            .clicked()
+                        {
+                            action = Some(SearchAction::SelectRegion(*index));
+                        }
+                    }
+                }
+            });
+        self.search.open = open;
+
+        let len = query.chars().count();
+        let replacement = self.search.replacement.clone();
+        let current = self.search.current.min(matches.len().saturating_sub(1));
+        let mut jump = None;
+        match action {
+            Some(SearchAction::Next) if !matches.is_empty() => {
+                self.search.current = (current + 1) % matches.len();
+                jump = Some(self.search.current);
+            }
+            Some(SearchAction::Previous) if !matches.is_empty() => {
+                self.search.current = (current + matches.len() - 1) % matches.len();
+                jump = Some(self.search.current);
+            }
+            Some(SearchAction::Replace) | Some(SearchAction::ReplaceAll) if !matches.is_empty() => {
+                let targets = if matches!(action, Some(SearchAction::ReplaceAll)) {
+                    matches.clone()
+                } else {
+                    vec![matches[current]]
+                };
+                if let Some(grid) = self.active_matrix_grid() {
+                    for &(row, col) in &targets {
+                        replace_cells(&mut grid.matrix[row], col, len, &replacement);
+                    }
+                    grid.modified = true;
+                }
+                self.log(&format!("🔁 Replaced {} match(es)", targets.len()));
+                matches = self
+                    .active_matrix_grid()
+                    .map(|grid| find_matches(&grid.matrix, &query, case_sensitive))
+                    .unwrap_or_default();
+            }
+            Some(SearchAction::SelectRegion(index)) => {
+                self.show_bounding_boxes = true;
+                self.region_edit_mode = true;
+                self.selected_region = Some(index);
+            }
+            _ => {}
+        }
+
+        let current = self.search.current.min(matches.len().saturating_sub(1));
+        if let Some(grid) = self.active_matrix_grid() {
+            grid.highlights = matches.iter().map(|&(row, col)| (row, col, len)).collect();
+            grid.current_highlight = (!matches.is_empty()).then_some(current);
+            if let Some(i) = jump {
+                grid.scroll_to = matches.get(i).copied();
+            }
+        }
+    }
+
+    fn show_export_dialog(&mut self, ctx: &egui::Context) {
+        if let Some(Ok(folder)) = self.export_folder_receiver.as_ref().map(|rx| rx.try_recv()) {
+            self.export_folder_receiver = None;
+            if folder.is_some() {
+                self.export_dialog.destination = folder;
+            }
+        }
+        if let Some(Ok(result)) = self.export_receiver.as_ref().map(|rx| rx.try_recv()) {
+            self.export_receiver = None;
+            match result {
+                Ok(paths) => {
+                    for path in &paths {
+                        self.log(&format!("✅ Exported: {}", path.display()));
+                    }
+                    if paths.is_empty() {
+                        self.log("⚠️ Nothing to export for those pages");
+                    }
+                }
+                Err(e) => self.log(&format!("❌ Export failed: {}", e)),
+            }
+        }
+
+        if !self.export_dialog.open {
+            return;
+        }
+        let Some(pdf_path) = self.pdf_path.clone() else {
+            return;
+        };
+        let default_destination = pdf_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+
+        let mut open = true;
+        let mut start = false;
+        let mut choose_folder = false;
+        let exporting = self.export_receiver.is_some();
+        let has_notes = !self.annotations.is_empty();
+        let dialog = &mut self.export_dialog;
+        let page_range = &mut self.page_range;
+        egui::Window::new(RichText::new("EXPORT").color(palette().highlight).monospace())
+            .id(egui::Id::new("export_dialog"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .frame(
+                egui::Frame::none()
+                    .fill(palette().bg)
+                    .stroke(Stroke::new(1.0, palette().highlight))
+                    .inner_margin(egui::Margin::same(10.0)),
+            )
+            .show(ctx, |ui| {
+                ui.label(RichText::new("Format").color(palette().dim).monospace());
+                for kind in ExportKind::ALL {
+                    ui.radio_value(&mut dialog.kind, kind, kind.label());
+                }
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Pages").color(palette().dim).monospace());
+                    ui.add(
+                        egui::TextEdit::singleline(page_range)
+                            .font(egui::TextStyle::Monospace)
+                            .hint_text("all, or e.g. 1-3,7")
+                            .desired_width(160.0),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("To").color(palette().dim).monospace());
+                    let destination = dialog.destination.as_ref().unwrap_or(&default_destination);
+                    ui.label(RichText::new(destination.display().to_string()).monospace().size(10.0));
+                    if ui.small_button("Choose…").clicked() {
+                        choose_folder = true;
+                    }
+                });
+                ui.add_enabled(
+                    has_notes,
+                    egui::Checkbox::new(&mut dialog.include_notes, "Include notes (.notes.md)"),
+                );
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.add_enabled_ui(!exporting, |ui| {
+                        if ui
+                            .button(RichText::new("Export").color(palette().fg).monospace())
+                            .clicked()
+                        {
+                            start = true;
+                        }
+                    });
+                    if exporting {
+                        ui.spinner();
+                    }
+                });
+            });
+        self.export_dialog.open = open;
+
+        if choose_folder && self.export_folder_receiver.is_none() {
+            let ctx_clone = ctx.clone();
+            let (tx, rx) = std::sync::mpsc::channel();
+            self.export_folder_receiver = Some(rx);
+            std::thread::spawn(move || {
+                let _ = tx.send(rfd::FileDialog::new().pick_folder());
+                ctx_clone.request_repaint();
+            });
+        }
+
+        if start {
+            let pages = if self.page_range.trim().is_empty() {
+                Ok((0..self.total_pages).collect())
+            } else {
+                export::parse_page_range(&self.page_range, self.total_pages)
+            };
+            let pages = match pages {
+                Ok(mut pages) => {
+                    pages.sort_unstable();
+                    pages
+                }
+                Err(e) => {
+                    self.log(&format!("❌ {}", e));
+                    return;
+                }
+            };
+            let job = ExportJob {
+                pdf_path,
+                kind: self.export_dialog.kind,
+                pages,
+                destination: self
+                    .export_dialog
+                    .destination
+                    .clone()
+                    .unwrap_or(default_destination),
+                edited_page: self.edited_page(),
+                notes: self
+                    .export_dialog
+                    .include_notes
+                    .then(|| self.annotations.clone()),
+                only_region: None,
+            };
+            self.start_export(ctx, job);
+        }
+    }
+
+    /// The page on screen with its matrix edits, for exports to use as is.
+    fn edited_page(&self) -> Option<(usize, CharacterMatrix)> {
+        self.matrix_result.character_matrix.clone().map(|mut m| {
+            if let Some(editable) = &self.matrix_result.editable_matrix {
+                m.matrix = editable.clone();
+            }
+            (self.current_page, m)
+        })
+    }
+
+    /// Run an export on the runtime; `show_export_dialog` reports the result.
+    fn start_export(&mut self, ctx: &egui::Context, job: ExportJob) {
+        if self.export_receiver.is_some() {
+            self.log("⏳ An export is already running");
+            return;
+        }
+        self.log(&format!(
+            "📤 Exporting {} page(s) as {}...",
+            job.pages.len(),
+            job.kind.label()
+        ));
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.export_receiver = Some(rx);
+        let ctx = ctx.clone();
+        self.runtime.spawn(async move {
+            let result = Self::run_export(job).await;
+            let _ = tx.send(result);
+            ctx.request_repaint();
+        });
+    }
+
+    async fn run_export(job: ExportJob) -> Result<Vec<PathBuf>, String> {
+        // Re-extracted pages get their hand-edited regions back, as on screen
+        let mut overrides = RegionOverrides::load(&job.pdf_path)
+            .map_err(|e| format!("Could not read region edits: {}", e))?;
+        let mut matrices = Vec::new();
+        for &page in &job.pages {
+            let matrix = match &job.edited_page {
+                Some((edited, matrix)) if *edited == page => matrix.clone(),
+                _ => {
+                    // The dialog shows its own progress, so nobody watches this one
+                    let (progress, _) = tokio::sync::watch::channel("");
+                    let mut matrix =
+                        Self::process_pdf_async(job.pdf_path.clone(), page, progress).await?;
+                    if let Some(regions) = overrides.pages.remove(&(page + 1)) {
+                        matrix.text_regions = regions;
+                    }
+                    matrix
+                }
+            };
+            matrices.push((page, matrix));
+        }
+
+        let stem = job
+            .pdf_path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "export".to_string());
+        std::fs::create_dir_all(&job.destination).map_err(|e| e.to_string())?;
+        let write = |name: String, content: &[u8]| -> Result<PathBuf, String> {
+            let path = job.destination.join(name);
+            std::fs::write(&path, content).map_err(|e| format!("{}: {}", path.display(), e))?;
+            Ok(path)
+        };
+
+        let mut written = Vec::new();
+        match job.kind {
+            ExportKind::Document(format) => {
+                let doc = export::ExtractedDocument {
+                    source: job.pdf_path.display().to_string(),
+                    pages: matrices
+                        .iter()
+                        .map(|(page, m)| {
+                            // Carry the (possibly hand-edited) blocks along with the text
+                            let mut extracted =
+                                export::ExtractedPage::from_matrix(page + 1, &m.matrix);
+                            extracted.regions =
+                                m.text_regions.iter().map(TextRegion::to_extracted).collect();
+                            extracted
+                        })
+                        .collect(),
+                };
+                let content = export::render(&doc, format).map_err(|e| e.to_string())?;
+                written.push(write(
+                    format!("{}.{}", stem, format.extension()),
+                    content.as_bytes(),
+                )?);
+            }
+            ExportKind::TableCsv => {
+                for (page, m) in &matrices {
+                    let wanted = |r: &&TextRegion| match job.only_region {
+                        Some(id) => r.region_id == id,
+                        None => r.block_type == BlockType::Table,
+                    };
+                    for region in m.text_regions.iter().filter(wanted) {
+                        let table = region
+                            .table
+                            .clone()
+                            .unwrap_or_else(|| TableGrid::from_text(&region.text_content));
+                        written.push(write(
+                            format!("{}.page{}.table{}.csv", stem, page + 1, region.region_id + 1),
+                            table.to_csv().as_bytes(),
+                        )?);
+                    }
+                }
+            }
+            ExportKind::PngOverlay => {
+                for (page, m) in &matrices {
+                    let path = job.destination.join(format!("{}.page{}.png", stem, page + 1));
+                    let mut image = render_page_image(&job.pdf_path, *page, 150.0)
+                        .map_err(|e| e.to_string())?
+                        .to_rgb8();
+                    if m.width > 0 && m.height > 0 {
+                        let cell_w = image.width() as f32 / m.width as f32;
+                        let cell_h = image.height() as f32 / m.height as f32;
+                        for region in &m.text_regions {
+                            let rect = [
+                                (region.bbox.x as f32 * cell_w) as u32,
+                                (region.bbox.y as f32 * cell_h) as u32,
+                                ((region.bbox.x + region.bbox.width) as f32 * cell_w) as u32,
+                                ((region.bbox.y + region.bbox.height) as f32 * cell_h) as u32,
+                            ];
+                            draw_rect_outline(&mut image, rect, Rgb([22, 160, 133]), 2);
+                        }
+                    }
+                    image.save(&path).map_err(|e| e.to_string())?;
+                    written.push(path);
+                }
+            }
+        }
+
+        if let Some(notes) = &job.notes {
+            let markdown = notes.to_markdown(&job.pdf_path.display().to_string(), &job.pages);
+            if !markdown.is_empty() {
+                written.push(write(format!("{}.notes.md", stem), markdown.as_bytes())?);
+            }
+        }
+        Ok(written)
+    }
+
+    fn show_table_editor(&mut self, ctx: &egui::Context) {
+        let Some(index) = self.table_editor else {
+            return;
+        };
+        let Some(region) = self
+            .matrix_result
+            .character_matrix
+            .as_mut()
+            .and_then(|m| m.text_regions.get_mut(index))
+        else {
+            self.table_editor = None;
+            return;
+        };
+        let region_id = region.region_id;
+        let table = region
+            .table
+            .get_or_insert_with(|| TableGrid::from_text(&region.text_content));
+
+        let mut open = true;
+        let mut changed = false;
+        let mut typed = false;
+        let mut resplit = false;
+        let mut export_csv = false;
+        egui::Window::new(
+            RichText::new(format!("TABLE R{}", region_id + 1))
+                .color(palette().highlight)
+                .monospace(),
+        )
+        .id(egui::Id::new("table_editor"))
+        .open(&mut open)
+        .default_size([520.0, 320.0])
+        .frame(
+            egui::Frame::none()
+                .fill(palette().bg)
+                .stroke(Stroke::new(1.0, palette().highlight))
+                .inner_margin(egui::Margin::same(8.0)),
+        )
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if ui.button(RichText::new("+ Row").monospace()).clicked() {
+                    table.add_row();
+                    changed = true;
+                }
+                if ui.button(RichText::new("+ Col").monospace()).clicked() {
+                    table.add_column();
+                    changed = true;
+                }
+                ui.label(RichText::new("│").color(palette().chrome).monospace());
+                let rows = table.cells.len();
+                changed |= ui
+                    .add(egui::DragValue::new(&mut table.header_rows).clamp_range(0..=rows))
+                    .changed();
+                ui.label(RichText::new("header rows").color(palette().dim).monospace());
+                ui.label(RichText::new("│").color(palette().chrome).monospace());
+                if ui
+                    .button(RichText::new("Re-split").monospace())
+                    .on_hover_text("Rebuild the grid from the region's matrix text")
+                    .clicked()
+                {
+                    resplit = true;
+                }
+                if ui.button(RichText::new("Export CSV").monospace()).clicked() {
+                    export_csv = true;
+                }
+                if ui.button(RichText::new("Copy Markdown").monospace()).clicked() {
+                    ui.output_mut(|o| o.copied_text = table.to_markdown());
                 }
+            });
+            ui.separator();
+
+            let mut remove_row = None;
+            let mut remove_column = None;
+            egui::ScrollArea::both().show(ui, |ui| {
+                egui::Grid::new("table_editor_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for column in 0..table.columns() {
+                            if ui.small_button("×").on_hover_text("Remove column").clicked() {
+                                remove_column = Some(column);
+                            }
+                        }
+                        ui.end_row();
+
+                        let header_rows = table.header_rows;
+                        for (row_idx, row) in table.cells.iter_mut().enumerate() {
+                            for cell in row.iter_mut() {
+                                let mut edit = egui::TextEdit::singleline(cell)
+                                    .font(egui::TextStyle::Monospace)
+                                    .desired_width(90.0);
+                                if row_idx < header_rows {
+                                    edit = edit.text_color(palette().highlight);
+                                }
+                                typed |= ui.add(edit).changed();
+                            }
+                            if ui.small_button("×").on_hover_text("Remove row").clicked() {
+                                remove_row = Some(row_idx);
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+            if let Some(row) = remove_row {
+                table.remove_row(row);
+                changed = true;
             }
-        })
-        .await;
+            if let Some(column) = remove_column {
+                table.remove_column(column);
+                changed = true;
+            }
+        });
 
-        match result {
-            Ok(pdf_result) => pdf_result,
-            Err(join_err) => Err(format!("PDF processing task failed: {}", join_err)),
+        if resplit {
+            region.table = Some(TableGrid::from_text(&region.text_content));
+            changed = true;
+        }
+        if !open {
+            self.table_editor = None;
+        }
+        if let (true, Some(pdf_path)) = (export_csv, self.pdf_path.clone()) {
+            let destination = self.export_dialog.destination.clone().unwrap_or_else(|| {
+                pdf_path.parent().map(Path::to_path_buf).unwrap_or_default()
+            });
+            let job = ExportJob {
+                pdf_path,
+                kind: ExportKind::TableCsv,
+                pages: vec![self.current_page],
+                destination,
+                edited_page: self.edited_page(),
+                notes: None,
+                only_region: Some(region_id),
+            };
+            self.start_export(ctx, job);
+        }
+        if changed {
+            self.save_region_overrides();
+        } else if typed {
+            self.queue_region_save();
         }
     }
 
-    async fn extract_simple_text_matrix(
-        pdf_path: &PathBuf,
-        page_index: usize,
-    ) -> Result<CharacterMatrix, String> {
-        let output = tokio::process::Command::new("mutool")
-            .arg("draw")
-            .arg("-F")
-            .arg("text")
-            .arg(pdf_path)
-            .arg((page_index + 1).to_string())
-            .output()
-            .await
-            .map_err(|e| format!("Failed to run mutool: {}", e))?;
-
-        if !output.status.success() {
-            return Err("Mutool extraction failed".to_string());
+    fn draw_confidence_heatmap(&self, ui: &mut egui::Ui, image_rect: Rect) {
+        let Some(char_matrix) = &self.matrix_result.character_matrix else {
+            return;
+        };
+        if char_matrix.width == 0 || char_matrix.height == 0 {
+            return;
         }
-
-        let text = String::from_utf8_lossy(&output.stdout);
-        let lines: Vec<&str> = text.lines().collect();
-        let max_width = lines.iter().map(|line| line.len()).max().unwrap_or(80);
-        let height = lines.len().max(25);
-
-        let mut matrix = vec![vec![' '; max_width]; height];
-
-        for (y, line) in lines.iter().enumerate() {
-            if y < height {
-                for (x, ch) in line.chars().enumerate() {
-                    if x < max_width {
-                        matrix[y][x] = ch;
-                    }
-                }
+        let cell = egui::vec2(
+            image_rect.width() / char_matrix.width as f32,
+            image_rect.height() / char_matrix.height as f32,
+        );
+        let painter = ui.painter_at(image_rect);
+        for region in &char_matrix.text_regions {
+            if region.confidence >= self.heatmap_threshold {
+                continue;
             }
+            let rect = Rect::from_min_size(
+                image_rect.min
+                    + egui::vec2(region.bbox.x as f32 * cell.x, region.bbox.y as f32 * cell.y),
+                egui::vec2(
+                    region.bbox.width as f32 * cell.x,
+                    region.bbox.height as f32 * cell.y,
+                ),
+            );
+            painter.rect_filled(rect, 0.0, heat_color(region.confidence));
         }
-
-        Ok(CharacterMatrix {
-            width: max_width,
-            height,
-            matrix,
-            text_regions: Vec::new(),
-            original_text: lines.iter().map(|s| s.to_string()).collect(),
-            char_width: 8.0,
-            char_height: 12.0,
-        })
     }
 
-    fn save_edited_matrix(&mut self) {
-        if let Some(editable_matrix) = &self.matrix_result.editable_matrix {
-            if let Some(pdf_path) = &self.pdf_path {
-                let output_path = pdf_path.with_extension("matrix.txt");
-
-                let mut content = String::new();
-                for row in editable_matrix {
-                    for ch in row {
-                        content.push(*ch);
-                    }
-                    content.push('\n');
-                }
+    /// Draw each extracted character at the cell it was mapped to, so text that
+    /// landed in the wrong place stands out against the page underneath.
+    fn draw_matrix_text_overlay(&self, ui: &mut egui::Ui, image_rect: Rect) {
+        let Some(char_matrix) = &self.matrix_result.character_matrix else {
+            return;
+        };
+        if char_matrix.width == 0 || char_matrix.height == 0 {
+            return;
+        }
+        let matrix = self
+            .matrix_result
+            .editable_matrix
+            .as_ref()
+            .unwrap_or(&char_matrix.matrix);
+        let cell = egui::vec2(
+            image_rect.width() / char_matrix.width as f32,
+            image_rect.height() / char_matrix.height as f32,
+        );
+        let font_id = FontId::monospace((cell.y * 0.9).max(4.0));
+        let painter = ui.painter_at(image_rect);
+        let color = palette().yellow.gamma_multiply(self.text_overlay_opacity);
+        let backing = palette().bg.gamma_multiply(self.text_overlay_opacity * 0.5);
 
-                match std::fs::write(&output_path, content) {
-                    Ok(_) => {
-                        self.log(&format!(
-                            "✅ Saved edited matrix to: {}",
-                            output_path.display()
-                        ));
-                        self.matrix_result.matrix_dirty = false;
-                    }
-                    Err(e) => {
-                        self.log(&format!("❌ Failed to save matrix: {}", e));
-                    }
+        for (row_idx, row) in matrix.iter().enumerate() {
+            for (col_idx, &ch) in row.iter().enumerate() {
+                if ch == ' ' || ch == '·' {
+                    continue;
                 }
+                let cell_rect = Rect::from_min_size(
+                    image_rect.min
+                        + egui::vec2(col_idx as f32 * cell.x, row_idx as f32 * cell.y),
+                    cell,
+                );
+                painter.rect_filled(cell_rect, 0.0, backing);
+                painter.text(
+                    cell_rect.center(),
+                    Align2::CENTER_CENTER,
+                    ch,
+                    font_id.clone(),
+                    color,
+                );
             }
         }
     }
 
-    fn draw_character_matrix_overlay(&self, ui: &mut egui::Ui, image_response: &egui::Response) {
+    fn draw_character_matrix_overlay(&self, ui: &mut egui::Ui, image_rect: Rect) {
         if let Some(char_matrix) = &self.matrix_result.character_matrix {
             let painter = ui.painter();
-            let image_rect = image_response.rect;
 
             let pdf_width_pts = char_matrix.width as f32 * char_matrix.char_width;
             let pdf_height_pts = char_matrix.height as f32 * char_matrix.char_height;
@@ -1764,7 +5285,7 @@ impl Chonker5App {
             let scale_x = image_rect.width() / pdf_width_pts;
             let scale_y = image_rect.height() / pdf_height_pts;
 
-            let grid_color = TERM_DIM.gamma_multiply(0.2);
+            let grid_color = palette().dim.gamma_multiply(0.2);
 
             for x in (0..char_matrix.width).step_by(10) {
                 let screen_x = image_rect.left() + (x as f32 * char_matrix.char_width * scale_x);
@@ -1799,12 +5320,16 @@ impl Chonker5App {
                             char_matrix.char_height * scale_y,
                         ),
                     );
-                    painter.rect_filled(cell_rect, 0.0, TERM_HIGHLIGHT.gamma_multiply(0.2));
-                    painter.rect_stroke(cell_rect, 0.0, egui::Stroke::new(2.0, TERM_HIGHLIGHT));
+                    painter.rect_filled(cell_rect, 0.0, palette().highlight.gamma_multiply(0.2));
+                    painter.rect_stroke(
+                        cell_rect,
+                        0.0,
+                        egui::Stroke::new(2.0, palette().highlight),
+                    );
                 }
             }
 
-            for region in char_matrix.text_regions.iter() {
+            for (index, region) in char_matrix.text_regions.iter().enumerate() {
                 let x1 =
                     image_rect.left() + (region.bbox.x as f32 * char_matrix.char_width * scale_x);
                 let y1 =
@@ -1816,27 +5341,66 @@ impl Chonker5App {
 
                 if rect.intersects(image_rect) {
                     let color = if region.confidence > 0.8 {
-                        TERM_HIGHLIGHT
+                        palette().highlight
                     } else if region.confidence > 0.5 {
-                        TERM_YELLOW
+                        palette().yellow
                     } else {
-                        TERM_DIM
+                        palette().dim
                     };
 
-                    painter.rect_stroke(rect, 0.0, egui::Stroke::new(2.0, color));
+                    if self.selected_region == Some(index) {
+                        painter.rect_filled(rect, 0.0, palette().blue.gamma_multiply(0.15));
+                        painter.rect_stroke(rect, 0.0, egui::Stroke::new(3.0, palette().blue));
+                        if self.region_edit_mode {
+                            // Resize handle sits on the bottom-right cell
+                            let handle = egui::Rect::from_min_max(
+                                egui::pos2(
+                                    x2 - char_matrix.char_width * scale_x,
+                                    y2 - char_matrix.char_height * scale_y,
+                                ),
+                                egui::pos2(x2, y2),
+                            );
+                            painter.rect_filled(handle, 0.0, palette().blue);
+                        }
+                    } else {
+                        painter.rect_stroke(rect, 0.0, egui::Stroke::new(2.0, color));
+                    }
 
                     if rect.width() > 20.0 && rect.height() > 15.0 {
                         let label_pos = rect.min + egui::vec2(2.0, 2.0);
+                        let label = if region.block_type == BlockType::Paragraph {
+                            format!("R{}", region.region_id + 1)
+                        } else {
+                            format!("R{} {}", region.region_id + 1, region.block_type.label())
+                        };
                         painter.text(
                             label_pos,
                             egui::Align2::LEFT_TOP,
-                            format!("R{}", region.region_id + 1),
+                            label,
                             FontId::monospace(10.0),
                             color,
                         );
                     }
                 }
             }
+
+            if let Some(RegionDrag::Draw { start, end }) = &self.region_drag {
+                let bbox = CharBBox::from_corners(*start, *end);
+                let min = image_rect.min
+                    + egui::vec2(
+                        bbox.x as f32 * char_matrix.char_width * scale_x,
+                        bbox.y as f32 * char_matrix.char_height * scale_y,
+                    );
+                let size = egui::vec2(
+                    bbox.width as f32 * char_matrix.char_width * scale_x,
+                    bbox.height as f32 * char_matrix.char_height * scale_y,
+                );
+                painter.rect_stroke(
+                    egui::Rect::from_min_size(min, size),
+                    0.0,
+                    egui::Stroke::new(2.0, palette().green),
+                );
+            }
         }
     }
 }
@@ -1846,11 +5410,11 @@ fn draw_terminal_frame(
     is_focused: bool,
     add_contents: impl FnOnce(&mut egui::Ui),
 ) {
-    let stroke_color = if is_focused { TERM_HIGHLIGHT } else { CHROME };
+    let stroke_color = if is_focused { palette().highlight } else { palette().chrome };
     let stroke_width = if is_focused { 2.0 } else { 1.0 };
 
     let frame = egui::Frame::none()
-        .fill(TERM_BG)
+        .fill(palette().bg)
         .stroke(Stroke::new(stroke_width, stroke_color))
         .inner_margin(egui::Margin::same(5.0))
         .outer_margin(egui::Margin::same(1.0))
@@ -1867,11 +5431,11 @@ fn draw_terminal_box(
     is_focused: bool,
     add_contents: impl FnOnce(&mut egui::Ui),
 ) {
-    let stroke_color = if is_focused { TERM_HIGHLIGHT } else { CHROME };
+    let stroke_color = if is_focused { palette().highlight } else { palette().chrome };
     let stroke_width = if is_focused { 2.0 } else { 1.0 };
 
     let frame = egui::Frame::none()
-        .fill(TERM_BG)
+        .fill(palette().bg)
         .stroke(Stroke::new(stroke_width, stroke_color))
         .inner_margin(egui::Margin::same(5.0))
         .outer_margin(egui::Margin::same(1.0))
@@ -1879,17 +5443,17 @@ fn draw_terminal_box(
 
     frame.show(ui, |ui| {
         ui.horizontal(|ui| {
-            ui.label(RichText::new("▸").color(TERM_HIGHLIGHT).monospace());
+            ui.label(RichText::new("▸").color(palette().highlight).monospace());
             ui.label(
                 RichText::new(title)
-                    .color(if is_focused { TERM_HIGHLIGHT } else { CHROME })
+                    .color(if is_focused { palette().highlight } else { palette().chrome })
                     .monospace()
                     .strong(),
             );
             if is_focused {
                 ui.label(
                     RichText::new(" [ACTIVE]")
-                        .color(TERM_HIGHLIGHT)
+                        .color(palette().highlight)
                         .monospace()
                         .size(10.0),
                 );
@@ -1908,6 +5472,45 @@ impl eframe::App for Chonker5App {
         }
 
         self.process_file_dialog_result(ctx);
+        self.process_font_dialog_result(ctx);
+        self.handle_dropped_files(ctx);
+
+        for grid in [&mut self.raw_text_matrix_grid, &mut self.ferrules_matrix_grid]
+            .into_iter()
+            .flatten()
+        {
+            grid.font_size = self.matrix_font_size;
+            grid.has_focus = self.focused_pane == FocusedPane::MatrixView;
+        }
+        if let Some(grid) = &mut self.raw_text_matrix_grid {
+            grid.cell_tints = match &self.matrix_result.character_matrix {
+                Some(char_matrix) if self.show_heatmap => char_matrix
+                    .confidence_map()
+                    .into_iter()
+                    .map(|row| {
+                        row.into_iter()
+                            .map(|c| c.filter(|&c| c < self.heatmap_threshold).map(heat_color))
+                            .collect()
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+            if let Some(notes) = self.annotations.pages.get(&(self.current_page + 1)) {
+                let rows = grid.matrix.len();
+                let cols = grid.matrix.first().map_or(0, Vec::len);
+                grid.cell_tints.resize(rows, Vec::new());
+                for row in &mut grid.cell_tints {
+                    row.resize(cols, None);
+                }
+                for note in notes {
+                    for y in note.bbox.y..(note.bbox.y + note.bbox.height).min(rows) {
+                        for x in note.bbox.x..(note.bbox.x + note.bbox.width).min(cols) {
+                            grid.cell_tints[y][x] = Some(palette().yellow.gamma_multiply(0.3));
+                        }
+                    }
+                }
+            }
+        }
 
         // Handle global keyboard shortcuts
         if self.focused_pane != FocusedPane::MatrixView {
@@ -1926,6 +5529,13 @@ impl eframe::App for Chonker5App {
                                 egui::Key::S if self.matrix_result.matrix_dirty => {
                                     self.save_edited_matrix()
                                 }
+                                egui::Key::F => {
+                                    self.search.open = true;
+                                    self.search.focus_query = true;
+                                }
+                                egui::Key::E if self.pdf_path.is_some() => {
+                                    self.export_dialog.open = true;
+                                }
                                 egui::Key::D => {
                                     self.pdf_dark_mode = !self.pdf_dark_mode;
                                     self.render_current_page(ctx);
@@ -1955,6 +5565,13 @@ impl eframe::App for Chonker5App {
                                 egui::Key::S if self.matrix_result.matrix_dirty => {
                                     self.save_edited_matrix()
                                 }
+                                egui::Key::F => {
+                                    self.search.open = true;
+                                    self.search.focus_query = true;
+                                }
+                                egui::Key::E if self.pdf_path.is_some() => {
+                                    self.export_dialog.open = true;
+                                }
                                 _ => {}
                             }
                         }
@@ -1963,28 +5580,52 @@ impl eframe::App for Chonker5App {
             });
         }
 
+        self.handle_navigation_keys(ctx);
+        self.save_settings_if_changed(ctx);
+
         if self.needs_render {
             self.needs_render = false;
             self.render_current_page(ctx);
         }
 
+        HIGH_CONTRAST.store(self.high_contrast, std::sync::atomic::Ordering::Relaxed);
+        self.apply_ui_scale(ctx);
+
         // Set up terminal style
+        let colors = palette();
+        // High contrast keeps widget fills black and draws heavier outlines
+        let (fills, stroke_width) = if self.high_contrast {
+            ([Color32::BLACK; 3], 2.0)
+        } else {
+            (
+                [
+                    Color32::from_rgb(20, 25, 30),
+                    Color32::from_rgb(30, 40, 45),
+                    Color32::from_rgb(40, 50, 55),
+                ],
+                1.0,
+            )
+        };
         let mut style = (*ctx.style()).clone();
         style.visuals.dark_mode = true;
-        style.visuals.override_text_color = Some(TERM_FG);
-        style.visuals.window_fill = TERM_BG;
-        style.visuals.panel_fill = TERM_BG;
-        style.visuals.extreme_bg_color = TERM_BG;
-        style.visuals.widgets.noninteractive.bg_fill = TERM_BG;
-        style.visuals.widgets.noninteractive.fg_stroke = Stroke::new(1.0, TERM_FG);
-        style.visuals.widgets.inactive.bg_fill = Color32::from_rgb(20, 25, 30);
-        style.visuals.widgets.inactive.bg_stroke = Stroke::new(1.0, CHROME);
-        style.visuals.widgets.hovered.bg_fill = Color32::from_rgb(30, 40, 45);
-        style.visuals.widgets.hovered.bg_stroke = Stroke::new(1.0, TERM_HIGHLIGHT);
-        style.visuals.widgets.active.bg_fill = Color32::from_rgb(40, 50, 55);
-        style.visuals.widgets.active.bg_stroke = Stroke::new(1.0, TERM_HIGHLIGHT);
-        style.visuals.selection.bg_fill = Color32::from_rgb(0, 150, 140);
-        style.visuals.selection.stroke = Stroke::new(1.0, TERM_HIGHLIGHT);
+        style.visuals.override_text_color = Some(colors.fg);
+        style.visuals.window_fill = colors.bg;
+        style.visuals.panel_fill = colors.bg;
+        style.visuals.extreme_bg_color = colors.bg;
+        style.visuals.widgets.noninteractive.bg_fill = colors.bg;
+        style.visuals.widgets.noninteractive.fg_stroke = Stroke::new(1.0, colors.fg);
+        style.visuals.widgets.inactive.bg_fill = fills[0];
+        style.visuals.widgets.inactive.bg_stroke = Stroke::new(stroke_width, colors.chrome);
+        style.visuals.widgets.hovered.bg_fill = fills[1];
+        style.visuals.widgets.hovered.bg_stroke = Stroke::new(stroke_width, colors.highlight);
+        style.visuals.widgets.active.bg_fill = fills[2];
+        style.visuals.widgets.active.bg_stroke = Stroke::new(stroke_width, colors.highlight);
+        style.visuals.selection.bg_fill = if self.high_contrast {
+            Color32::from_rgb(0, 90, 160)
+        } else {
+            Color32::from_rgb(0, 150, 140)
+        };
+        style.visuals.selection.stroke = Stroke::new(stroke_width, colors.highlight);
         ctx.set_style(style);
 
         // Handle focus switching
@@ -2013,30 +5654,26 @@ impl eframe::App for Chonker5App {
         });
 
         // Check for async results
-        if let Some(mut receiver) = self.vision_receiver.take() {
-            if let Ok(result) = receiver.try_recv() {
-                match result {
-                    Ok(character_matrix) => {
-                        self.matrix_result.character_matrix = Some(character_matrix.clone());
-                        self.matrix_result.editable_matrix = Some(character_matrix.matrix.clone());
-                        self.matrix_result.original_matrix = Some(character_matrix.matrix.clone());
-                        self.matrix_result.is_loading = false;
-                        self.matrix_result.matrix_dirty = false;
-                        self.log("✅ Character matrix extraction completed");
-                    }
-                    Err(e) => {
-                        self.matrix_result.error = Some(e);
-                        self.matrix_result.is_loading = false;
-                    }
-                }
+        self.poll_extraction();
+
+        if let Some(edited) = self.notes_edited {
+            if edited.elapsed() >= EDIT_SAVE_DELAY {
+                self.flush_annotations();
+            } else {
+                ctx.request_repaint_after(EDIT_SAVE_DELAY);
+            }
+        }
+        if let Some((edited, ..)) = &self.region_edits {
+            if edited.elapsed() >= EDIT_SAVE_DELAY {
+                self.flush_region_edits();
             } else {
-                self.vision_receiver = Some(receiver);
+                ctx.request_repaint_after(EDIT_SAVE_DELAY);
             }
         }
 
         // Main UI
         egui::CentralPanel::default()
-            .frame(egui::Frame::none().fill(TERM_BG))
+            .frame(egui::Frame::none().fill(palette().bg))
             .show(ctx, |ui| {
                 // Header controls
                 ui.horizontal(|ui| {
@@ -2048,88 +5685,180 @@ impl eframe::App for Chonker5App {
 
                     ui.label(
                         RichText::new("CHONKER 5")
-                            .color(TERM_HIGHLIGHT)
+                            .color(palette().highlight)
                             .monospace()
                             .size(16.0)
                             .strong()
                     );
 
-                    ui.label(RichText::new("│").color(CHROME).monospace());
+                    ui.label(RichText::new("│").color(palette().chrome).monospace());
 
-                    if ui.button(RichText::new("[O] Open").color(TERM_FG).monospace().size(12.0)).clicked() {
-                        self.open_file(ctx);
-                    }
+                    ui.menu_button(RichText::new("[O] File").color(palette().fg).monospace().size(12.0), |ui| {
+                        if ui.button(RichText::new("Open…").monospace()).clicked() {
+                            ui.close_menu();
+                            self.open_file(ctx);
+                        }
+                        ui.separator();
+                        ui.label(RichText::new("Recent").color(palette().dim).monospace().size(10.0));
+                        if self.recent.entries().is_empty() {
+                            ui.label(RichText::new("(none)").color(palette().dim).monospace().size(10.0));
+                        }
+                        let mut chosen = None;
+                        for entry in self.recent.entries() {
+                            let name = entry
+                                .path
+                                .file_name()
+                                .map_or_else(|| entry.path.display().to_string(), |n| n.to_string_lossy().to_string());
+                            if ui
+                                .button(RichText::new(format!("{}  p.{}", name, entry.last_page + 1)).monospace())
+                                .on_hover_text(entry.path.display().to_string())
+                                .clicked()
+                            {
+                                chosen = Some(entry.path.clone());
+                            }
+                        }
+                        if let Some(path) = chosen {
+                            ui.close_menu();
+                            self.load_pdf(ctx, path);
+                        }
+                    }).response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, "File menu"));
+
+                    ui.add_enabled_ui(self.pdf_path.is_some(), |ui| {
+                        if accessible(ui.button(RichText::new("[E] Export").color(palette().fg).monospace().size(12.0)), "Export").clicked() {
+                            self.export_dialog.open = true;
+                        }
+                    });
 
-                    ui.label(RichText::new("│").color(CHROME).monospace());
+                    ui.label(RichText::new("│").color(palette().chrome).monospace());
 
                     // Navigation
                     ui.add_enabled_ui(self.pdf_path.is_some() && self.current_page > 0, |ui| {
-                        if ui.button(RichText::new("←").color(TERM_FG).monospace().size(12.0)).clicked() {
-                            self.current_page = self.current_page.saturating_sub(1);
-                            self.matrix_result.character_matrix = None;
-                            self.ferrules_output_cache = None;
-                            self.ferrules_matrix_grid = None;
-                            self.render_current_page(ctx);
-                            self.extract_character_matrix(ctx);
+                        if accessible(ui.button(RichText::new("←").color(palette().fg).monospace().size(12.0)), "Previous page").clicked() {
+                            self.go_to_page(ctx, self.current_page.saturating_sub(1));
                         }
                     });
 
                     if self.pdf_path.is_some() {
                         ui.label(RichText::new(format!("{}/{}", self.current_page + 1, self.total_pages))
-                            .color(TERM_FG)
+                            .color(palette().fg)
                             .monospace()
                             .size(12.0));
                     }
 
                     ui.add_enabled_ui(self.pdf_path.is_some() && self.current_page < self.total_pages - 1, |ui| {
-                        if ui.button(RichText::new("→").color(TERM_FG).monospace().size(12.0)).clicked() {
-                            self.current_page += 1;
-                            self.matrix_result.character_matrix = None;
-                            self.ferrules_output_cache = None;
-                            self.ferrules_matrix_grid = None;
-                            self.render_current_page(ctx);
-                            self.extract_character_matrix(ctx);
+                        if accessible(ui.button(RichText::new("→").color(palette().fg).monospace().size(12.0)), "Next page").clicked() {
+                            self.go_to_page(ctx, self.current_page + 1);
                         }
                     });
 
-                    ui.label(RichText::new("│").color(CHROME).monospace());
+                    ui.label(RichText::new("│").color(palette().chrome).monospace());
 
                     // Zoom controls
                     ui.add_enabled_ui(self.pdf_path.is_some(), |ui| {
-                        if ui.button(RichText::new("-").color(TERM_FG).monospace().size(12.0)).clicked() {
+                        if accessible(ui.button(RichText::new("-").color(palette().fg).monospace().size(12.0)), "Zoom out").clicked() {
                             self.zoom_level = (self.zoom_level - 0.25).max(0.5);
                             self.render_current_page(ctx);
                         }
 
                         ui.label(RichText::new(format!("{}%", (self.zoom_level * 100.0) as i32))
-                            .color(TERM_FG)
+                            .color(palette().fg)
                             .monospace()
                             .size(12.0));
 
-                        if ui.button(RichText::new("+").color(TERM_FG).monospace().size(12.0)).clicked() {
+                        if accessible(ui.button(RichText::new("+").color(palette().fg).monospace().size(12.0)), "Zoom in").clicked() {
                             self.zoom_level = (self.zoom_level + 0.25).min(3.0);
                             self.render_current_page(ctx);
                         }
+
+                        let scroll_text = if self.continuous_scroll { "[C]✓" } else { "[C]" };
+                        if accessible_toggle(ui.button(RichText::new(scroll_text).color(palette().fg).monospace().size(12.0)), "Continuous scrolling", self.continuous_scroll)
+                            .on_hover_text("Continuous scrolling through all pages")
+                            .clicked() {
+                            self.continuous_scroll = !self.continuous_scroll;
+                            if self.continuous_scroll {
+                                self.scroll_to_page = Some(self.current_page);
+                            } else {
+                                self.render_current_page(ctx);
+                            }
+                        }
                     });
 
-                    ui.label(RichText::new("│").color(CHROME).monospace());
+                    ui.label(RichText::new("│").color(palette().chrome).monospace());
 
                     ui.add_enabled_ui(self.pdf_path.is_some(), |ui| {
-                        if ui.button(RichText::new("[M]").color(TERM_FG).monospace().size(12.0)).clicked() {
+                        if accessible(ui.button(RichText::new("[M]").color(palette().fg).monospace().size(12.0)), "Extract character matrix").clicked() {
                             self.extract_character_matrix(ctx);
                             self.active_tab = ExtractionTab::RawText;
                         }
 
-                        ui.label(RichText::new("│").color(CHROME).monospace());
+                        ui.label(RichText::new("│").color(palette().chrome).monospace());
 
                         let bbox_text = if self.show_bounding_boxes { "[B]✓" } else { "[B]" };
-                        if ui.button(RichText::new(bbox_text).color(TERM_FG).monospace().size(12.0)).clicked() {
+                        if accessible_toggle(ui.button(RichText::new(bbox_text).color(palette().fg).monospace().size(12.0)), "Region boxes", self.show_bounding_boxes).clicked() {
                             self.show_bounding_boxes = !self.show_bounding_boxes;
                         }
 
-                        ui.label(RichText::new("│").color(CHROME).monospace());
+                        let edit_text = if self.region_edit_mode { "[R]✓" } else { "[R]" };
+                        if accessible_toggle(ui.button(RichText::new(edit_text).color(palette().fg).monospace().size(12.0)), "Edit regions", self.region_edit_mode)
+                            .on_hover_text("Edit region boxes: drag on empty space to draw, drag a box to move it, drag its corner to resize, Delete to remove")
+                            .clicked() {
+                            self.region_edit_mode = !self.region_edit_mode;
+                            self.show_bounding_boxes |= self.region_edit_mode;
+                            self.annotate_mode &= !self.region_edit_mode;
+                            self.selected_region = None;
+                            self.region_drag = None;
+                        }
+
+                        let notes_text = if self.annotate_mode { "[N]✓" } else { "[N]" };
+                        if accessible_toggle(ui.button(RichText::new(notes_text).color(palette().fg).monospace().size(12.0)), "Annotate", self.annotate_mode)
+                            .on_hover_text("Annotate: drag on the page to highlight, then add a comment")
+                            .clicked() {
+                            self.annotate_mode = !self.annotate_mode;
+                            self.region_edit_mode &= !self.annotate_mode;
+                            self.annotation_drag = None;
+                        }
+
+                        let list_text = if self.show_block_list { "[L]✓" } else { "[L]" };
+                        if accessible_toggle(ui.button(RichText::new(list_text).color(palette().fg).monospace().size(12.0)), "Block list", self.show_block_list)
+                            .on_hover_text("List detected blocks; click one to zoom to it")
+                            .clicked() {
+                            self.show_block_list = !self.show_block_list;
+                        }
+
+                        ui.label(RichText::new("│").color(palette().chrome).monospace());
+                        let overlay_text = if self.text_overlay { "[V]✓" } else { "[V]" };
+                        if accessible_toggle(ui.button(RichText::new(overlay_text).color(palette().fg).monospace().size(12.0)), "Text overlay", self.text_overlay)
+                            .on_hover_text("Overlay the extracted characters on the page")
+                            .clicked() {
+                            self.text_overlay = !self.text_overlay;
+                        }
+                        if self.text_overlay {
+                            ui.add(egui::Slider::new(&mut self.text_overlay_opacity, 0.1..=1.0)
+                                .show_value(false))
+                                .on_hover_text("Overlay opacity");
+                        }
+
+                        ui.label(RichText::new("│").color(palette().chrome).monospace());
+                        let heatmap_text = if self.show_heatmap { "[H]✓" } else { "[H]" };
+                        if accessible_toggle(ui.button(RichText::new(heatmap_text).color(palette().fg).monospace().size(12.0)), "Confidence heatmap", self.show_heatmap)
+                            .on_hover_text("Placement confidence of each character; PDFium extractions only")
+                            .clicked() {
+                            self.show_heatmap = !self.show_heatmap;
+                        }
+                        if self.show_heatmap {
+                            ui.add(egui::Slider::new(&mut self.heatmap_threshold, 0.0..=1.0)
+                                .max_decimals(2))
+                                .on_hover_text("Hide cells at or above this confidence");
+                            ui.checkbox(&mut self.heatmap_on_page, "page");
+                            // mutool's plain text has no positions to score
+                            if self.matrix_result.character_matrix.as_ref().is_some_and(|m| m.text_regions.is_empty()) {
+                                ui.label(RichText::new("no scores for this page").color(palette().dim).monospace().size(10.0));
+                            }
+                        }
+
+                        ui.label(RichText::new("│").color(palette().chrome).monospace());
                         let dark_text = if self.pdf_dark_mode { "[D]✓" } else { "[D]" };
-                        if ui.button(RichText::new(dark_text).color(TERM_FG).monospace().size(12.0))
+                        if accessible_toggle(ui.button(RichText::new(dark_text).color(palette().fg).monospace().size(12.0)), "Dark page", self.pdf_dark_mode)
                             .on_hover_text("Toggle light/dark mode for PDF")
                             .clicked() {
                             self.pdf_dark_mode = !self.pdf_dark_mode;
@@ -2137,15 +5866,31 @@ impl eframe::App for Chonker5App {
                         }
 
                         if self.matrix_result.matrix_dirty {
-                            ui.label(RichText::new("│").color(CHROME).monospace());
-                            if ui.button(RichText::new("[S] Save").color(TERM_YELLOW).monospace().size(12.0)).clicked() {
+                            ui.label(RichText::new("│").color(palette().chrome).monospace());
+                            if accessible(ui.button(RichText::new("[S] Save").color(palette().yellow).monospace().size(12.0)), "Save edited matrix").clicked() {
                                 self.save_edited_matrix();
                             }
                         }
                     });
+
+                    ui.label(RichText::new("│").color(palette().chrome).monospace());
+                    ui.menu_button(RichText::new("[A]").color(palette().fg).monospace().size(12.0), |ui| {
+                        ui.label(RichText::new("UI scale").color(palette().dim).monospace().size(10.0));
+                        ui.add(egui::Slider::new(&mut self.ui_scale, UI_SCALE_MIN..=UI_SCALE_MAX)
+                            .step_by(0.05)
+                            .custom_formatter(|scale, _| format!("{:.0}%", scale * 100.0)))
+                            .widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Slider, "UI scale"));
+                        if ui.button("Reset scale").clicked() {
+                            self.ui_scale = 1.0;
+                        }
+                        ui.separator();
+                        ui.checkbox(&mut self.high_contrast, "High contrast");
+                    }).response.on_hover_text("Accessibility: UI scale and high contrast")
+                        .widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, "Accessibility menu"));
                 });
 
                 ui.add_space(2.0);
+                self.show_document_tabs(ui);
 
                 // Main content area
                 if self.pdf_path.is_some() {
@@ -2164,6 +5909,14 @@ impl eframe::App for Chonker5App {
                             egui::Layout::left_to_right(egui::Align::TOP),
                             |ui| {
                                 draw_terminal_frame(ui, self.focused_pane == FocusedPane::PdfView, |ui| {
+                                    if self.continuous_scroll {
+                                        if ui.ui_contains_pointer() && ui.input(|i| i.pointer.any_click()) {
+                                            self.focused_pane = FocusedPane::PdfView;
+                                        }
+                                        self.show_continuous_pages(ui, ctx);
+                                        return;
+                                    }
+
                                     egui::ScrollArea::both()
                                         .auto_shrink([false; 2])
                                         .show(ui, |ui| {
@@ -2185,10 +5938,33 @@ impl eframe::App for Chonker5App {
 
                                                 ui.vertical_centered(|ui| {
                                                     let response = ui.image(egui::load::SizedTexture::new(texture_id, display_size));
+                                                    response.widget_info(|| egui::WidgetInfo::labeled(
+                                                        egui::WidgetType::Other,
+                                                        format!("Page {} of {}", self.current_page + 1, self.total_pages),
+                                                    ));
+
+                                                    if let Some(index) = self.scroll_to_region.take() {
+                                                        if let Some(rect) = self.region_screen_rect(index, response.rect) {
+                                                            ui.scroll_to_rect(rect, Some(egui::Align::Center));
+                                                        }
+                                                    }
 
+                                                    if self.show_heatmap && self.heatmap_on_page {
+                                                        self.draw_confidence_heatmap(ui, response.rect);
+                                                    }
+                                                    if self.text_overlay {
+                                                        self.draw_matrix_text_overlay(ui, response.rect);
+                                                    }
                                                     if self.show_bounding_boxes {
-                                                        self.draw_character_matrix_overlay(ui, &response);
+                                                        if self.region_edit_mode {
+                                                            self.handle_region_editing(ui, response.rect);
+                                                        }
+                                                        self.draw_character_matrix_overlay(ui, response.rect);
+                                                    }
+                                                    if self.annotate_mode {
+                                                        self.handle_annotation_drawing(ui, response.rect);
                                                     }
+                                                    self.draw_annotations(ui, response.rect);
 
                                                     if response.hovered() {
                                                         let zoom_delta = ui.input(|i| i.zoom_delta());
@@ -2220,7 +5996,7 @@ impl eframe::App for Chonker5App {
                                             } else {
                                                 ui.centered_and_justified(|ui| {
                                                     ui.label(RichText::new("Loading page...")
-                                                        .color(TERM_DIM)
+                                                        .color(palette().dim)
                                                         .monospace());
                                                 });
                                             }
@@ -2238,9 +6014,9 @@ impl eframe::App for Chonker5App {
                         let separator_response = ui.allocate_rect(separator_rect, egui::Sense::drag());
 
                         let separator_color = if separator_response.hovered() {
-                            TERM_HIGHLIGHT
+                            palette().highlight
                         } else {
-                            CHROME
+                            palette().chrome
                         };
                         ui.painter().rect_filled(separator_response.rect, 0.0, separator_color);
 
@@ -2249,7 +6025,7 @@ impl eframe::App for Chonker5App {
                             ui.painter().circle_filled(
                                 egui::pos2(center.x, center.y + i as f32 * 10.0),
                                 1.5,
-                                TERM_DIM
+                                palette().dim
                             );
                         }
 
@@ -2286,22 +6062,58 @@ impl eframe::App for Chonker5App {
                                             if self.focused_pane == FocusedPane::MatrixView && self.selected_cell.is_some() {
                                                 label.push_str(" ⌨️");
                                             }
-                                            RichText::new(label).color(TERM_HIGHLIGHT).monospace()
+                                            RichText::new(label).color(palette().highlight).monospace()
                                         } else {
-                                            RichText::new(" Raw Text ").color(TERM_DIM).monospace()
+                                            RichText::new(" Raw Text ").color(palette().dim).monospace()
                                         };
                                         if ui.button(matrix_label).clicked() {
                                             self.active_tab = ExtractionTab::RawText;
                                         }
 
                                         let ferrules_label = if self.active_tab == ExtractionTab::SmartLayout {
-                                            RichText::new("[SMART LAYOUT]").color(TERM_HIGHLIGHT).monospace()
+                                            RichText::new("[SMART LAYOUT]").color(palette().highlight).monospace()
                                         } else {
-                                            RichText::new(" Smart Layout ").color(TERM_DIM).monospace()
+                                            RichText::new(" Smart Layout ").color(palette().dim).monospace()
                                         };
                                         if ui.button(ferrules_label).clicked() {
                                             self.active_tab = ExtractionTab::SmartLayout;
                                         }
+
+                                        let compare_label = if self.active_tab == ExtractionTab::Compare {
+                                            RichText::new("[COMPARE]").color(palette().highlight).monospace()
+                                        } else {
+                                            RichText::new(" Compare ").color(palette().dim).monospace()
+                                        };
+                                        if ui.button(compare_label)
+                                            .on_hover_text("PDFium-only matrix beside Smart Layout, differing cells highlighted")
+                                            .clicked() {
+                                            self.active_tab = ExtractionTab::Compare;
+                                        }
+
+                                        ui.label(RichText::new("│").color(palette().chrome).monospace());
+                                        ui.add(egui::DragValue::new(&mut self.matrix_font_size)
+                                            .clamp_range(6.0..=24.0)
+                                            .speed(0.25)
+                                            .suffix("pt"))
+                                            .on_hover_text("Matrix font size")
+                                            .widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::DragValue, "Matrix font size"));
+                                        let font_label = self.matrix_font_path
+                                            .as_ref()
+                                            .and_then(|path| path.file_stem())
+                                            .map_or("Font…".to_string(), |stem| stem.to_string_lossy().to_string());
+                                        if ui.button(RichText::new(font_label).color(palette().fg).monospace())
+                                            .on_hover_text("Pick a monospace font file for the matrix")
+                                            .clicked() {
+                                            self.pick_matrix_font(ctx);
+                                        }
+                                        if self.matrix_font_path.is_some()
+                                            && accessible(ui.small_button("×"), "Use the built-in font")
+                                                .on_hover_text("Back to the built-in font").clicked() {
+                                            self.matrix_font_path = None;
+                                            if let Err(e) = Self::install_matrix_font(ctx, None) {
+                                                self.log(&format!("❌ Failed to reset font: {}", e));
+                                            }
+                                        }
                                     });
 
                                     ui.separator();
@@ -2315,14 +6127,9 @@ impl eframe::App for Chonker5App {
                                                 ExtractionTab::RawText => {
                                                     // Raw text matrix editing view
                                                     if self.matrix_result.is_loading {
-                                                        ui.centered_and_justified(|ui| {
-                                                            ui.spinner();
-                                                            ui.label(RichText::new("\nExtracting raw text...")
-                                                                .color(TERM_FG)
-                                                                .monospace());
-                                                        });
+                                                        self.show_extraction_progress(ui, "Extracting raw text...");
                                                     } else if let Some(error) = &self.matrix_result.error {
-                                                        ui.label(RichText::new(error).color(TERM_ERROR).monospace());
+                                                        ui.label(RichText::new(error).color(palette().error).monospace());
                                                     } else if let Some(character_matrix) = &self.matrix_result.character_matrix {
                                                         // Create or update the matrix grid for Raw Text
                                                         if self.matrix_result.editable_matrix.is_none() {
@@ -2347,12 +6154,12 @@ impl eframe::App for Chonker5App {
                                                             self.raw_text_matrix_grid = Some(MatrixGrid::new(&matrix_text));
                                                         }
                                                         
-                                                        ui.label(RichText::new("Click to place cursor. Click and drag to select. Drag selection to move. Type to edit. Ctrl+C/X/V for copy/cut/paste.")
-                                                            .color(TERM_DIM)
+                                                        ui.label(RichText::new("Click or use arrows to place cursor. Drag or Shift+arrows to select. Drag selection to move. Type to edit. Ctrl+C/X/V for copy/cut/paste (multi-column copies are tab-separated; Ctrl+Shift+C for plain text).")
+                                                            .color(palette().dim)
                                                             .size(10.0));
                                                         
                                                         egui::Frame::none()
-                                                            .fill(Color32::from_rgb(10, 15, 20))
+                                                            .fill(palette().bg)
                                                             .show(ui, |ui| {
                                                                 egui::ScrollArea::both()
                                                                     .auto_shrink([false; 2])
@@ -2381,48 +6188,30 @@ impl eframe::App for Chonker5App {
                                                             self.current_page + 1,
                                                             character_matrix.text_regions.len(),
                                                             character_matrix.original_text.len()))
-                                                            .color(TERM_DIM)
+                                                            .color(palette().dim)
                                                             .monospace()
                                                             .size(10.0));
                                                     } else {
                                                         ui.centered_and_justified(|ui| {
                                                             ui.label(RichText::new("No character matrix yet\n\nPress [M] to extract")
-                                                                .color(TERM_DIM)
+                                                                .color(palette().dim)
                                                                 .monospace());
                                                         });
                                                     }
                                                 }
+                                                ExtractionTab::Compare => self.show_engine_comparison(ui),
                                                 ExtractionTab::SmartLayout => {
                                                     // Ferrules smart layout view
                                                     if let Some(pdf_path) = self.pdf_path.clone() {
-                                                        if self.ferrules_output_cache.is_none() {
-                                                            self.log(&format!("🔄 Running Ferrules for page {}...", self.current_page + 1));
-                                                            match self.matrix_engine.run_ferrules_integration_test(&pdf_path) {
-                                                                Ok(console_output) => {
-                                                                    let page_output = format!(
-                                                                        "📄 Page {}/{}\n{}",
-                                                                        self.current_page + 1,
-                                                                        self.total_pages,
-                                                                        console_output
-                                                                    );
-                                                                    self.ferrules_output_cache = Some(page_output.clone());
-                                                                    self.ferrules_matrix_grid = Some(MatrixGrid::new(&console_output));
-                                                                    self.log("✅ Ferrules analysis complete");
-                                                                }
-                                                                Err(e) => {
-                                                                    self.ferrules_output_cache = Some(format!("❌ Terminal command failed: {}", e));
-                                                                    self.log(&format!("❌ Ferrules failed: {}", e));
-                                                                }
-                                                            }
-                                                        }
+                                                        self.ensure_ferrules_output(&pdf_path);
 
                                                         if let Some(matrix_grid) = &mut self.ferrules_matrix_grid {
-                                                            ui.label(RichText::new("Click to place cursor. Click and drag to select. Drag selection to move. Type to edit. Ctrl+C/X/V for copy/cut/paste.")
-                                                                .color(TERM_DIM)
+                                                            ui.label(RichText::new("Click or use arrows to place cursor. Drag or Shift+arrows to select. Drag selection to move. Type to edit. Ctrl+C/X/V for copy/cut/paste (multi-column copies are tab-separated; Ctrl+Shift+C for plain text).")
+                                                                .color(palette().dim)
                                                                 .size(10.0));
 
                                                             egui::Frame::none()
-                                                                .fill(Color32::from_rgb(10, 15, 20))
+                                                                .fill(palette().bg)
                                                                 .show(ui, |ui| {
                                                                     egui::ScrollArea::both()
                                                                         .auto_shrink([false; 2])
@@ -2445,14 +6234,14 @@ impl eframe::App for Chonker5App {
                                                             ui.centered_and_justified(|ui| {
                                                                 ui.spinner();
                                                                 ui.label(RichText::new("\nPreparing Ferrules analysis...")
-                                                                    .color(TERM_FG)
+                                                                    .color(palette().fg)
                                                                     .monospace());
                                                             });
                                                         }
                                                     } else {
                                                         ui.centered_and_justified(|ui| {
                                                             ui.label(RichText::new("No PDF loaded")
-                                                                .color(TERM_DIM)
+                                                                .color(palette().dim)
                                                                 .monospace());
                                                         });
                                                     }
@@ -2467,14 +6256,27 @@ impl eframe::App for Chonker5App {
                     // No PDF loaded
                     draw_terminal_box(ui, "WELCOME", false, |ui| {
                         ui.centered_and_justified(|ui| {
-                            ui.label(RichText::new("🐹 CHONKER 5\n\nCharacter Matrix PDF Representation\n\nPress [O] to open a PDF file\n\nThen [M] to create character matrix")
-                                .color(TERM_FG)
+                            ui.label(RichText::new("🐹 CHONKER 5\n\nCharacter Matrix PDF Representation\n\nPress [O] to open a PDF file, or drop one here\n\nThen [M] to create character matrix")
+                                .color(palette().fg)
                                 .monospace()
                                 .size(16.0));
                         });
                     });
                 }
             });
+
+        self.show_region_inspector(ctx);
+        self.show_table_editor(ctx);
+        self.show_block_list(ctx);
+        self.show_annotation_panel(ctx);
+        self.show_search_panel(ctx);
+        self.show_export_dialog(ctx);
+        self.show_discard_confirmation(ctx);
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.flush_annotations();
+        self.flush_region_edits();
     }
 }
 
@@ -2532,6 +6334,102 @@ mod tests {
         assert_eq!(zero_bbox.area(), 0);
     }
 
+    #[test]
+    fn test_char_bbox_from_corners() {
+        let bbox = CharBBox::from_corners((7, 2), (3, 4));
+        assert_eq!((bbox.x, bbox.y, bbox.width, bbox.height), (3, 2, 5, 3));
+
+        let matrix: Vec<Vec<char>> = ["abcdefgh", "ij  mnop", "qrstuvwx"]
+            .iter()
+            .map(|row| row.chars().collect())
+            .collect();
+        assert_eq!(CharBBox::from_corners((1, 0), (3, 1)).text_in(&matrix), "bcd\nj");
+    }
+
+    #[test]
+    fn test_region_block_type_defaults_and_round_trips() {
+        // Regions saved before block types existed still load
+        let json = r#"{"bbox":{"x":1,"y":2,"width":3,"height":1},"confidence":1.0,"text_content":"abc","region_id":0}"#;
+        let mut region: TextRegion = serde_json::from_str(json).unwrap();
+        assert_eq!(region.block_type, BlockType::Paragraph);
+
+        region.block_type = BlockType::Table;
+        let mut overrides = RegionOverrides::default();
+        overrides.pages.insert(3, vec![region]);
+        let reloaded: RegionOverrides =
+            serde_json::from_str(&serde_json::to_string(&overrides).unwrap()).unwrap();
+        assert_eq!(reloaded.pages[&3][0].block_type, BlockType::Table);
+    }
+
+    #[test]
+    fn test_table_grid_from_text_and_export() {
+        let mut table = TableGrid::from_text("Item    Qty   Note\n\nBolts   40    a, b\nNuts    7\n");
+        assert_eq!(table.header_rows, 1);
+        assert_eq!(table.columns(), 3);
+        assert_eq!(table.cells[2], vec!["Nuts", "7", ""]);
+        assert_eq!(table.to_csv(), "Item,Qty,Note\nBolts,40,\"a, b\"\nNuts,7,\n");
+        assert_eq!(
+            table.to_markdown(),
+            "| Item | Qty | Note |\n| --- | --- | --- |\n| Bolts | 40 | a, b |\n| Nuts | 7 |  |\n"
+        );
+
+        table.remove_column(2);
+        table.remove_row(0);
+        table.add_row();
+        assert_eq!(table.header_rows, 1);
+        assert_eq!(table.cells, vec![vec!["Bolts", "40"], vec!["Nuts", "7"], vec!["", ""]]);
+    }
+
+    #[test]
+    fn test_find_and_replace_cells() {
+        let mut matrix: Vec<Vec<char>> = ["Total  TOTAL", "subtotal"]
+            .iter()
+            .map(|row| row.chars().collect())
+            .collect();
+        assert_eq!(find_matches(&matrix, "total", false), vec![(0, 0), (0, 7), (1, 3)]);
+        assert_eq!(find_matches(&matrix, "Total", true), vec![(0, 0)]);
+        assert!(find_matches(&matrix, "", false).is_empty());
+
+        replace_cells(&mut matrix[0], 0, 5, "Sum");
+        assert_eq!(matrix[0].iter().collect::<String>(), "Sum    TOTAL");
+        replace_cells(&mut matrix[1], 3, 5, "amount!!");
+        assert_eq!(matrix[1].iter().collect::<String>(), "subamoun");
+    }
+
+    #[test]
+    fn test_confidence_map_takes_lowest_overlap() {
+        let region = |x, y, width, height, confidence| TextRegion {
+            bbox: CharBBox {
+                x,
+                y,
+                width,
+                height,
+            },
+            confidence,
+            text_content: String::new(),
+            region_id: 0,
+            block_type: BlockType::default(),
+            table: None,
+        };
+        let mut matrix = CharacterMatrix::new(4, 2);
+        matrix.text_regions = vec![region(0, 0, 3, 1, 0.9), region(2, 0, 2, 2, 0.4)];
+
+        let map = matrix.confidence_map();
+        assert_eq!(map[0], vec![Some(0.9), Some(0.9), Some(0.4), Some(0.4)]);
+        assert_eq!(map[1], vec![None, None, Some(0.4), Some(0.4)]);
+    }
+
+    #[test]
+    fn test_placement_confidence() {
+        assert_eq!(placement_confidence('a', 3.0, 7.0, 1), 1.0);
+        // Snapped half a cell sideways, or shared with another character
+        assert!((placement_confidence('a', 3.5, 7.0, 1) - 0.5).abs() < 1e-6);
+        assert!((placement_confidence('a', 3.0, 7.0, 2) - 0.5).abs() < 1e-6);
+        // Glyphs without a Unicode mapping
+        assert!(placement_confidence('\u{fffd}', 3.0, 7.0, 1) < 0.5);
+        assert!(placement_confidence('\u{e001}', 3.0, 7.0, 1) < 0.5);
+    }
+
     #[test]
     fn test_character_matrix_engine_new() {
         let engine = CharacterMatrixEngine::new();
@@ -2557,4 +6455,99 @@ mod tests {
         assert_eq!(matrix.matrix[0].len(), 80);
         assert_eq!(matrix.original_text.len(), 1);
     }
+
+    #[test]
+    fn test_gui_settings_fill_missing_fields() {
+        let settings: GuiSettings =
+            serde_json::from_str(r#"{"split_ratio": 0.3, "active_tab": "SmartLayout"}"#).unwrap();
+        assert_eq!(settings.split_ratio, 0.3);
+        assert_eq!(settings.active_tab, ExtractionTab::SmartLayout);
+        assert_eq!(settings.zoom_level, 1.0);
+        assert!(settings.pdf_dark_mode);
+        assert_eq!(settings.ui_scale, 1.0);
+        assert!(!settings.high_contrast);
+    }
+
+    #[test]
+    fn test_smart_invert_keeps_figures() {
+        let mut page = image::RgbaImage::from_pixel(96, 48, image::Rgba([255, 255, 255, 255]));
+        page.put_pixel(5, 5, image::Rgba([0, 0, 0, 255]));
+        // A photo-like gradient in the right half
+        for y in 0..48 {
+            for x in 48..96 {
+                page.put_pixel(x, y, image::Rgba([(x * 2) as u8, 120, (y * 4) as u8, 255]));
+            }
+        }
+        let photo = *page.get_pixel(70, 30);
+        smart_invert(&mut page);
+
+        assert_eq!(page.get_pixel(0, 0).0, [0, 0, 0, 255]);
+        assert_eq!(page.get_pixel(5, 5).0, [255, 255, 255, 255]);
+        assert_eq!(*page.get_pixel(70, 30), photo);
+
+        // Hue survives: a light red highlight becomes a dark red
+        let mut pixel = image::Rgba([255, 200, 200, 255]);
+        invert_lightness(&mut pixel);
+        assert_eq!(pixel.0, [55, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_selection_to_tsv_splits_at_gutters() {
+        let rows: Vec<Vec<char>> = ["Item    Qty", "Bolts    40", "Nuts      7"]
+            .iter()
+            .map(|line| line.chars().collect())
+            .collect();
+        assert_eq!(
+            selection_to_tsv(&rows).as_deref(),
+            Some("Item\tQty\nBolts\t40\nNuts\t7")
+        );
+
+        // A single space between words is not a column boundary
+        let prose = vec!["Total assets".chars().collect::<Vec<char>>()];
+        assert_eq!(selection_to_tsv(&prose), None);
+    }
+
+    #[test]
+    fn test_annotations_to_markdown() {
+        let note = |quote: &str, comment: &str| Annotation {
+            bbox: CharBBox::from_corners((0, 0), (3, 0)),
+            quote: quote.to_string(),
+            comment: comment.to_string(),
+            author: "jg".to_string(),
+            created: "2025-09-01 10:00".to_string(),
+        };
+        let mut annotations = Annotations::default();
+        annotations.pages.insert(2, vec![note("Total  42", "check\nsource"), note("Net", "")]);
+        annotations.pages.insert(5, vec![note("Other", "")]);
+
+        let markdown = annotations.to_markdown("a.pdf", &[0, 1]);
+        assert_eq!(
+            markdown,
+            "# Notes: a.pdf\n\n## Page 2\n\n\
+             - \"Total 42\" — check source (jg 2025-09-01 10:00)\n\
+             - \"Net\" (jg 2025-09-01 10:00)\n"
+        );
+        assert!(annotations.to_markdown("a.pdf", &[0]).is_empty());
+
+        // Older sidecars without comment metadata still load
+        let parsed: Annotation = serde_json::from_str(
+            r#"{"bbox": {"x": 1, "y": 2, "width": 3, "height": 1}, "quote": "abc"}"#,
+        )
+        .unwrap();
+        assert!(parsed.comment.is_empty());
+    }
+
+    #[test]
+    fn test_cell_diff() {
+        let rows = |lines: &[&str]| -> Vec<Vec<char>> {
+            lines.iter().map(|l| l.chars().collect()).collect()
+        };
+        let diff = cell_diff(&rows(&["Total 42", "ab"]), &rows(&["Total 47", "ab  ", "x"]));
+        assert_eq!(diff.len(), 3);
+        assert_eq!(diff[0].iter().filter(|&&d| d).count(), 1);
+        assert!(diff[0][7]);
+        // Trailing blanks match missing cells
+        assert!(diff[1].iter().all(|&d| !d));
+        assert!(diff[2][0]);
+    }
 }
@@ -0,0 +1,2221 @@
+use crate::diff::DocumentDiff;
+use crate::export::{self, ExportFormat, ExtractedDocument, ExtractedPage};
+use crate::spatial::{ExtractOptions, Spatial};
+use anyhow::{anyhow, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use pdfium_render::prelude::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+// ============= HEADLESS CLI =============
+//
+// `chonker5-tui <subcommand> ...` runs without a terminal UI so extraction can
+// be scripted. Anything that isn't a subcommand falls through to the TUI.
+
+// Same fixed grid the TUI extracts into
+const MATRIX_WIDTH: usize = 200;
+const MATRIX_HEIGHT: usize = 100;
+
+// Bump when a field in the `--json` envelope changes meaning or is removed
+const JSON_SCHEMA_VERSION: u32 = 1;
+
+// Accepted by every subcommand, so they're stripped before its own parsing
+const GLOBAL_FLAGS: &[&str] = &["--json", "-q", "--quiet", "-v", "--verbose", "-vv"];
+
+const SUBCOMMANDS: &[&str] = &[
+    "extract",
+    "batch",
+    "watch",
+    "run",
+    "diff",
+    "validate",
+    "bench",
+    "convert",
+    "metadata",
+    "redact",
+    "split",
+    "merge",
+    "completions",
+];
+
+const USAGE: &str = "\
+Usage: chonker5-tui [tui] [FILE.pdf] [--page N] [--goto ROW:COL] [--cell-size WxH]
+       chonker5-tui extract INPUT.pdf|--stdin [--pages 3,7-12] [--rotate 90] [--crop x0,y0,x1,y1]
+                           [--format txt|md|json|csv|hocr] [--out DIR]
+       chonker5-tui batch 'GLOB' [--jobs N] [--pages 3,7-12] [--rotate 90] [--crop x0,y0,x1,y1]
+                         [--format FMT] [--out DIR]
+       chonker5-tui watch DIR [--format FMT] [--out DIR] [--interval SECS] [--debounce SECS]
+                         (SECS are whole seconds, at least 1)
+       chonker5-tui run PIPELINE.toml
+       chonker5-tui diff OLD.pdf|OLD.json NEW.pdf|NEW.json [--pages 1-20] [--format txt|json]
+       chonker5-tui validate INPUT.pdf|INPUT.json [--rules RULES.toml] [--pages 1-20]
+       chonker5-tui convert SAVED.txt|md|json|csv --to FMT [--from FMT] [--out PATH]
+       chonker5-tui metadata INPUT.pdf
+       chonker5-tui redact INPUT.pdf [--pattern REGEX] [--regions REGIONS.json] --out OUT.pdf
+       chonker5-tui split INPUT.pdf --every N [--out DIR]
+       chonker5-tui merge A.pdf B.pdf... -o OUT.pdf
+       chonker5-tui completions bash|zsh|fish
+       chonker5-tui bench INPUT.pdf... [--runs N] [--pages 1-20] [--format FMT]
+                         (times load, render, extract and export; there is no fuse stage)
+
+Every subcommand accepts --json to print a versioned result envelope instead,
+and -q (errors only), -v (per-file status) or -vv (per-page timings).
+Exit codes: 0 success, 1 some files or checks failed, 2 error.";
+
+pub fn is_subcommand(arg: &str) -> bool {
+    SUBCOMMANDS.contains(&arg) || arg == "help" || arg == "--help" || arg == "-h"
+}
+
+/// Run a subcommand and return the process exit code.
+pub fn run(args: &[String]) -> Result<i32> {
+    let json = args.iter().any(|a| a == "--json");
+    for arg in args {
+        match arg.as_str() {
+            "-q" | "--quiet" => VERBOSITY.store(0, Ordering::Relaxed),
+            "-v" | "--verbose" => VERBOSITY.store(verbosity().max(1) + 1, Ordering::Relaxed),
+            "-vv" => VERBOSITY.store(3, Ordering::Relaxed),
+            _ => {}
+        }
+    }
+    let args: Vec<String> = args
+        .iter()
+        .filter(|a| !GLOBAL_FLAGS.contains(&a.as_str()))
+        .cloned()
+        .collect();
+    let command = args.first().map_or("help", String::as_str);
+    let rest = &args[1.min(args.len())..];
+
+    let started = Instant::now();
+    let result = match command {
+        "extract" => extract(rest),
+        "batch" => batch(rest),
+        "watch" => watch(rest, json),
+        "run" => run_pipeline(rest),
+        "diff" => diff(rest),
+        "validate" => validate(rest),
+        "bench" => bench(rest),
+        "convert" => convert(rest),
+        "metadata" => metadata(rest),
+        "redact" => redact(rest),
+        "split" => split(rest),
+        "merge" => merge(rest),
+        "completions" => completions(rest),
+        _ => Report::new(
+            0,
+            format!("{}\n", USAGE),
+            serde_json::json!({ "usage": USAGE }),
+        ),
+    };
+
+    if !json {
+        return Ok(match result {
+            Ok(report) => {
+                for warning in &report.warnings {
+                    log(1, format!("Warning: {}", warning));
+                }
+                print!("{}", report.text);
+                report.code
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                2
+            }
+        });
+    }
+
+    let (report, error) = match result {
+        Ok(report) => (report, None),
+        Err(e) => (Report::empty(2), Some(e.to_string())),
+    };
+    let envelope = Envelope {
+        schema_version: JSON_SCHEMA_VERSION,
+        command,
+        status: match report.code {
+            0 => "ok",
+            1 => "failed",
+            _ => "error",
+        },
+        exit_code: report.code,
+        error,
+        warnings: &report.warnings,
+        timings: serde_json::json!({ "total_ms": started.elapsed().as_millis() as u64 }),
+        data: &report.data,
+    };
+    println!("{}", serde_json::to_string_pretty(&envelope)?);
+    Ok(report.code)
+}
+
+// ============= PROGRESS & VERBOSITY =============
+
+// 0 = errors only (-q), 1 = normal, 2 = per-file status (-v), 3 = per-page timings (-vv)
+static VERBOSITY: AtomicU8 = AtomicU8::new(1);
+
+fn verbosity() -> u8 {
+    VERBOSITY.load(Ordering::Relaxed)
+}
+
+/// Print a status line on stderr if the verbosity allows it.
+fn log(level: u8, message: impl AsRef<str>) {
+    Progress::hidden().log(level, message);
+}
+
+/// Progress bar on stderr. Hidden with `-q` and when stderr isn't a terminal,
+/// so piped runs only see the status lines.
+#[derive(Clone)]
+struct Progress(Option<ProgressBar>);
+
+impl Progress {
+    fn new(len: usize, unit: &str) -> Self {
+        if verbosity() == 0 || len < 2 {
+            return Self::hidden();
+        }
+        let bar = ProgressBar::new(len as u64);
+        bar.set_style(
+            ProgressStyle::with_template("{bar:30.cyan/blue} {pos}/{len} {msg} [{elapsed}]")
+                .unwrap(),
+        );
+        bar.set_message(unit.to_string());
+        Self(Some(bar))
+    }
+
+    fn hidden() -> Self {
+        Self(None)
+    }
+
+    /// Log above the bar so the two don't interleave.
+    fn log(&self, level: u8, message: impl AsRef<str>) {
+        if verbosity() < level {
+            return;
+        }
+        match &self.0 {
+            Some(bar) if !bar.is_hidden() => bar.println(message),
+            _ => eprintln!("{}", message.as_ref()),
+        }
+    }
+
+    fn inc(&self) {
+        if let Some(bar) = &self.0 {
+            bar.inc(1);
+        }
+    }
+
+    fn finish(&self) {
+        if let Some(bar) = &self.0 {
+            bar.finish_and_clear();
+        }
+    }
+}
+
+// ============= TUI LAUNCH =============
+
+/// Where to open the TUI: `[tui] [FILE.pdf] [--page N] [--goto ROW:COL]`.
+/// Page and cell are 1-based on the command line and 0-based here.
+pub struct LaunchOptions {
+    pub file: Option<PathBuf>,
+    pub page: Option<usize>,
+    pub goto: Option<(usize, usize)>,
+    /// Pixel size of one terminal cell, for terminals whose font isn't 8x18
+    pub cell_size: Option<(u16, u16)>,
+}
+
+impl LaunchOptions {
+    pub fn parse(args: &[String]) -> Result<Self> {
+        let args = match args.first() {
+            Some(first) if first == "tui" => &args[1..],
+            _ => args,
+        };
+        let args = ParsedArgs::parse(args, &["--page", "--goto", "--cell-size"], &[])?;
+
+        let page = match args.value("--page") {
+            Some(page) => match page.parse::<usize>()? {
+                0 => return Err(anyhow!("--page is 1-based")),
+                page => Some(page - 1),
+            },
+            None => None,
+        };
+        let goto = args.value("--goto").map(parse_cell).transpose()?;
+        let cell_size = args.value("--cell-size").map(parse_cell_size).transpose()?;
+
+        Ok(Self {
+            file: args.positional.first().map(PathBuf::from),
+            page,
+            goto,
+            cell_size,
+        })
+    }
+}
+
+/// Parse a 1-based `ROW:COL` matrix cell into 0-based indices.
+fn parse_cell(spec: &str) -> Result<(usize, usize)> {
+    let (row, col) = spec
+        .split_once(':')
+        .ok_or_else(|| anyhow!("--goto expects ROW:COL, got '{}'", spec))?;
+    match (row.trim().parse::<usize>()?, col.trim().parse::<usize>()?) {
+        (0, _) | (_, 0) => Err(anyhow!("--goto is 1-based, got '{}'", spec)),
+        (row, col) => Ok((row - 1, col - 1)),
+    }
+}
+
+/// Parse a `WxH` cell size in pixels, e.g. `10x20`.
+fn parse_cell_size(spec: &str) -> Result<(u16, u16)> {
+    let (width, height) = spec
+        .split_once('x')
+        .ok_or_else(|| anyhow!("--cell-size expects WxH, got '{}'", spec))?;
+    match (width.trim().parse::<u16>()?, height.trim().parse::<u16>()?) {
+        (0, _) | (_, 0) => Err(anyhow!("--cell-size must be non-zero, got '{}'", spec)),
+        size => Ok(size),
+    }
+}
+
+// ============= SHELL COMPLETIONS =============
+
+fn completions(args: &[String]) -> Result<Report> {
+    let args = ParsedArgs::parse(args, &[], &[])?;
+    let shell = args
+        .positional
+        .first()
+        .ok_or_else(|| anyhow!("completions needs a shell: bash, zsh or fish"))?;
+
+    let commands = std::iter::once("tui")
+        .chain(SUBCOMMANDS.iter().copied())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let flags = GLOBAL_FLAGS
+        .iter()
+        .chain(&["--page", "--goto", "--cell-size"])
+        .copied()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let script = match shell.as_str() {
+        "bash" => format!(
+            r#"_chonker5_tui() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    if [[ "$cur" == -* ]]; then
+        COMPREPLY=( $(compgen -W "{flags}" -- "$cur") )
+    elif [ "$COMP_CWORD" -eq 1 ]; then
+        COMPREPLY=( $(compgen -W "{commands}" -- "$cur") $(compgen -f -X '!*.pdf' -- "$cur") )
+    else
+        COMPREPLY=( $(compgen -f -- "$cur") )
+    fi
+}}
+complete -o filenames -F _chonker5_tui chonker5-tui
+"#
+        ),
+        "zsh" => format!(
+            r#"#compdef chonker5-tui
+if [[ "$PREFIX" == -* ]]; then
+    compadd -- {flags}
+elif (( CURRENT == 2 )); then
+    _alternatives 'commands:subcommand:({commands})' 'files:PDF file:_files -g "*.pdf"'
+else
+    _files
+fi
+"#
+        ),
+        "fish" => {
+            let mut script = format!(
+                "complete -c chonker5-tui -n '__fish_use_subcommand' -a '{}'\n",
+                commands
+            );
+            for flag in flags.split(' ') {
+                script.push_str(&match flag.strip_prefix("--") {
+                    Some(long) => format!("complete -c chonker5-tui -l {}\n", long),
+                    None if flag.len() == 2 => {
+                        format!("complete -c chonker5-tui -s {}\n", &flag[1..])
+                    }
+                    None => format!("complete -c chonker5-tui -o {}\n", &flag[1..]),
+                });
+            }
+            script
+        }
+        other => {
+            return Err(anyhow!(
+                "Unknown shell '{}' (expected bash, zsh or fish)",
+                other
+            ))
+        }
+    };
+    Report::new(0, script, serde_json::json!({ "shell": shell }))
+}
+
+// ============= RESULTS =============
+
+/// What a subcommand produced: `text` is printed in normal mode, `data` is
+/// wrapped in the `--json` envelope.
+struct Report {
+    code: i32,
+    text: String,
+    data: serde_json::Value,
+    warnings: Vec<String>,
+}
+
+impl Report {
+    fn new(code: i32, text: String, data: impl Serialize) -> Result<Self> {
+        Ok(Self {
+            code,
+            text,
+            data: serde_json::to_value(data)?,
+            warnings: Vec::new(),
+        })
+    }
+
+    fn empty(code: i32) -> Self {
+        Self {
+            code,
+            text: String::new(),
+            data: serde_json::Value::Null,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Envelope<'a> {
+    schema_version: u32,
+    command: &'a str,
+    /// "ok", "failed" (ran, but some files or checks failed) or "error"
+    status: &'a str,
+    exit_code: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    warnings: &'a [String],
+    timings: serde_json::Value,
+    data: &'a serde_json::Value,
+}
+
+// ============= ARGUMENT PARSING =============
+
+struct ParsedArgs {
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+    flags: Vec<String>,
+}
+
+impl ParsedArgs {
+    /// Split `args` into positionals, `--option value` pairs and bare flags.
+    /// Only names listed in `value_options` consume the following argument.
+    fn parse(args: &[String], value_options: &[&str], flag_options: &[&str]) -> Result<Self> {
+        let mut positional = Vec::new();
+        let mut options = HashMap::new();
+        let mut flags = Vec::new();
+        let mut iter = args.iter();
+
+        while let Some(arg) = iter.next() {
+            if let Some((name, value)) = arg.split_once('=').filter(|_| arg.starts_with("--")) {
+                options.insert(name.to_string(), value.to_string());
+            } else if value_options.contains(&arg.as_str()) {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| anyhow!("{} expects a value", arg))?;
+                options.insert(arg.clone(), value.clone());
+            } else if flag_options.contains(&arg.as_str()) {
+                flags.push(arg.clone());
+            } else if arg.starts_with('-') && arg.len() > 1 {
+                return Err(anyhow!("Unknown option '{}'\n\n{}", arg, USAGE));
+            } else {
+                positional.push(arg.clone());
+            }
+        }
+
+        Ok(Self {
+            positional,
+            options,
+            flags,
+        })
+    }
+
+    fn value(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    fn flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|f| f == name)
+    }
+
+    fn input(&self) -> Result<&str> {
+        self.positional
+            .first()
+            .map(String::as_str)
+            .ok_or_else(|| anyhow!("Missing input file\n\n{}", USAGE))
+    }
+}
+
+/// Read `--rotate` and `--crop x0,y0,x1,y1` (PDF points) into extract options.
+fn parse_extract_options(args: &ParsedArgs) -> Result<ExtractOptions> {
+    let rotate = match args.value("--rotate") {
+        Some(deg) => match deg.parse::<u16>()? {
+            r @ (0 | 90 | 180 | 270) => r,
+            other => return Err(anyhow!("--rotate must be 0, 90, 180 or 270, got {}", other)),
+        },
+        None => 0,
+    };
+
+    let crop = match args.value("--crop") {
+        Some(spec) => {
+            let coords = spec
+                .split(',')
+                .map(|v| v.trim().parse::<f32>())
+                .collect::<Result<Vec<_>, _>>()?;
+            match coords.as_slice() {
+                &[x0, y0, x1, y1] if x0 < x1 && y0 < y1 => Some([x0, y0, x1, y1]),
+                _ => {
+                    return Err(anyhow!(
+                        "--crop expects x0,y0,x1,y1 with x0 < x1 and y0 < y1"
+                    ))
+                }
+            }
+        }
+        None => None,
+    };
+
+    Ok(ExtractOptions { rotate, crop })
+}
+
+// ============= PDF HELPERS =============
+
+fn load_pdfium() -> Result<Pdfium> {
+    Ok(Pdfium::new(
+        Pdfium::bind_to_library(Pdfium::pdfium_platform_library_name_at_path("./lib/"))
+            .or_else(|_| Pdfium::bind_to_system_library())?,
+    ))
+}
+
+/// Extract `input` (optionally limited to a page range spec) into the shared
+/// document model.
+fn extract_file(
+    pdfium: &Pdfium,
+    input: &str,
+    page_spec: Option<&str>,
+    opts: &ExtractOptions,
+) -> Result<ExtractedDocument> {
+    let document = pdfium.load_pdf_from_file(input, None)?;
+    extract_document(&document, input, page_spec, opts, false)
+}
+
+fn extract_document(
+    document: &PdfDocument,
+    source: &str,
+    page_spec: Option<&str>,
+    opts: &ExtractOptions,
+    show_progress: bool,
+) -> Result<ExtractedDocument> {
+    let total_pages = document.pages().len() as usize;
+
+    let pages = match page_spec {
+        Some(spec) => export::parse_page_range(spec, total_pages)?,
+        None => (0..total_pages).collect(),
+    };
+
+    let progress = if show_progress {
+        Progress::new(pages.len(), "pages")
+    } else {
+        Progress::hidden()
+    };
+    let mut extracted = Vec::with_capacity(pages.len());
+    for page in pages {
+        let started = Instant::now();
+        let matrix = Spatial::extract_with(document, page, MATRIX_WIDTH, MATRIX_HEIGHT, opts)?;
+        extracted.push(ExtractedPage::from_matrix(page + 1, &matrix));
+        progress.log(
+            3,
+            format!("{} page {}: {:?}", source, page + 1, started.elapsed()),
+        );
+        progress.inc();
+    }
+    progress.finish();
+    Ok(ExtractedDocument {
+        source: source.to_string(),
+        pages: extracted,
+    })
+}
+
+fn output_in_dir(dir: &Path, input: &str, format: ExportFormat) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let stem = Path::new(input)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "output".to_string());
+    Ok(dir.join(format!("{}.{}", stem, format.extension())))
+}
+
+/// The literal directory a glob pattern starts from, before any wildcard.
+fn glob_base(pattern: &str) -> PathBuf {
+    Path::new(pattern)
+        .components()
+        .take_while(|c| !c.as_os_str().to_string_lossy().contains(['*', '?', '[']))
+        .collect()
+}
+
+/// Output paths for files matched by `pattern`, mirroring each input's path
+/// below the glob base inside `dir`, so `a/report.pdf` and `b/report.pdf`
+/// don't overwrite each other. Fails before anything is written if two
+/// inputs would still share an output, e.g. `x.pdf` and `x.PDF`.
+fn mirrored_outputs(
+    dir: &Path,
+    pattern: &str,
+    inputs: &[String],
+    format: ExportFormat,
+) -> Result<Vec<PathBuf>> {
+    let base = glob_base(pattern);
+    let mut seen: HashMap<PathBuf, &str> = HashMap::new();
+    let mut targets = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        let input_path = Path::new(input);
+        let relative = input_path
+            .strip_prefix(&base)
+            .ok()
+            .filter(|rel| !rel.as_os_str().is_empty())
+            .or_else(|| input_path.file_name().map(Path::new))
+            .ok_or_else(|| anyhow!("{} has no file name", input))?;
+        let target = dir.join(relative).with_extension(format.extension());
+        if let Some(other) = seen.insert(target.clone(), input) {
+            return Err(anyhow!(
+                "{} and {} would both be written to {}",
+                other,
+                input,
+                target.display()
+            ));
+        }
+        targets.push(target);
+    }
+    Ok(targets)
+}
+
+/// Write `target`, creating its directory first.
+fn write_creating_dirs(target: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(target, content)?;
+    Ok(())
+}
+
+/// Write `content` to `out` if it names a file, or to `out/<input stem>.<ext>`
+/// if it names a directory. Returns the path written.
+fn write_output(input: &str, out: &str, format: ExportFormat, content: &str) -> Result<PathBuf> {
+    let out_path = PathBuf::from(out);
+    let target = if out.ends_with('/') || out_path.is_dir() {
+        output_in_dir(&out_path, input, format)?
+    } else {
+        out_path
+    };
+
+    std::fs::write(&target, content)?;
+    log(1, format!("Wrote {}", target.display()));
+    Ok(target)
+}
+
+/// Write `content` to `--out` if given, otherwise hand it back as the text to
+/// print. Either way the `--json` data says where it went.
+fn deliver(
+    input: &str,
+    out: Option<&str>,
+    format: ExportFormat,
+    doc: &ExtractedDocument,
+    content: String,
+) -> Result<Report> {
+    let mut data = serde_json::json!({
+        "source": input,
+        "format": format.extension(),
+        "pages": doc.pages.len(),
+    });
+    let text = match out {
+        Some(out) => {
+            data["output"] = write_output(input, out, format, &content)?
+                .display()
+                .to_string()
+                .into();
+            String::new()
+        }
+        None => {
+            data["content"] = content.clone().into();
+            content
+        }
+    };
+    Report::new(0, text, data)
+}
+
+// ============= SUBCOMMANDS =============
+
+fn extract(args: &[String]) -> Result<Report> {
+    let args = ParsedArgs::parse(
+        args,
+        &["--pages", "--rotate", "--crop", "--format", "--out"],
+        &["--stdin"],
+    )?;
+    let format = ExportFormat::parse(args.value("--format").unwrap_or("txt"))?;
+    let opts = parse_extract_options(&args)?;
+    let pdfium = load_pdfium()?;
+
+    // `--stdin` (or `-` as the input) reads the PDF from a pipe
+    let from_stdin = args.flag("--stdin") || args.input().ok() == Some("-");
+    let (input, extracted) = if from_stdin {
+        let mut bytes = Vec::new();
+        std::io::stdin().read_to_end(&mut bytes)?;
+        let document = pdfium.load_pdf_from_byte_vec(bytes, None)?;
+        (
+            "stdin",
+            extract_document(&document, "stdin", args.value("--pages"), &opts, true)?,
+        )
+    } else {
+        let input = args.input()?;
+        let document = pdfium.load_pdf_from_file(input, None)?;
+        (
+            input,
+            extract_document(&document, input, args.value("--pages"), &opts, true)?,
+        )
+    };
+    let content = export::render(&extracted, format)?;
+
+    let mut report = deliver(input, args.value("--out"), format, &extracted, content)?;
+    for page in &extracted.pages {
+        if page.lines.iter().all(|line| line.is_empty()) {
+            report
+                .warnings
+                .push(format!("page {} has no extractable text", page.page));
+        }
+    }
+    Ok(report)
+}
+
+#[derive(Serialize)]
+struct BatchResult {
+    input: String,
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+fn batch(args: &[String]) -> Result<Report> {
+    let args = ParsedArgs::parse(
+        args,
+        &[
+            "--jobs", "--pages", "--rotate", "--crop", "--format", "--out",
+        ],
+        &[],
+    )?;
+    let pattern = args.input()?;
+    let format = ExportFormat::parse(args.value("--format").unwrap_or("txt"))?;
+    let opts = parse_extract_options(&args)?;
+    let jobs = match args.value("--jobs") {
+        Some(n) => n.parse::<usize>()?.max(1),
+        None => std::thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+
+    let inputs: Vec<String> = glob::glob(pattern)?
+        .filter_map(|entry| entry.ok())
+        .filter(|path| path.is_file())
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+    if inputs.is_empty() {
+        return Err(anyhow!("No files match '{}'", pattern));
+    }
+
+    let targets = match args.value("--out") {
+        Some(dir) => Some(mirrored_outputs(Path::new(dir), pattern, &inputs, format)?),
+        None => None,
+    };
+
+    // One pdfium binding shared by all workers; each worker loads its own documents
+    let pdfium = load_pdfium()?;
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(inputs.len()));
+    let progress = Progress::new(inputs.len(), "files");
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.min(inputs.len()) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(input) = inputs.get(index) else {
+                    break;
+                };
+
+                let outcome =
+                    extract_file(&pdfium, input, args.value("--pages"), &opts).and_then(|doc| {
+                        let target = match &targets {
+                            Some(targets) => targets[index].clone(),
+                            None => Path::new(input).with_extension(format.extension()),
+                        };
+                        write_creating_dirs(&target, &export::render(&doc, format)?)?;
+                        Ok(target)
+                    });
+
+                let result = match outcome {
+                    Ok(target) => BatchResult {
+                        input: input.clone(),
+                        ok: true,
+                        output: Some(target.display().to_string()),
+                        error: None,
+                    },
+                    Err(e) => BatchResult {
+                        input: input.clone(),
+                        ok: false,
+                        output: None,
+                        error: Some(e.to_string()),
+                    },
+                };
+                match &result.error {
+                    None => progress.log(2, format!("ok   {}", input)),
+                    Some(e) => progress.log(1, format!("FAIL {}: {}", input, e)),
+                }
+                progress.inc();
+                results.lock().unwrap().push((index, result));
+            });
+        }
+    });
+    progress.finish();
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(index, _)| *index);
+    let results: Vec<BatchResult> = results.into_iter().map(|(_, r)| r).collect();
+    let failed = results.iter().filter(|r| !r.ok).count();
+
+    let summary = serde_json::json!({
+        "total": results.len(),
+        "succeeded": results.len() - failed,
+        "failed": failed,
+        "files": results,
+    });
+    let text = format!("{}\n", serde_json::to_string_pretty(&summary)?);
+
+    Report::new(if failed > 0 { 1 } else { 0 }, text, summary)
+}
+
+/// Modification time (unix nanoseconds) and size used to detect changed files.
+/// Whole seconds would miss a file rewritten twice within the same second.
+type FileStamp = (u128, u64);
+
+fn file_stamp(path: &Path) -> Option<FileStamp> {
+    let meta = std::fs::metadata(path).ok()?;
+    let mtime = meta
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_nanos();
+    Some((mtime, meta.len()))
+}
+
+fn load_watch_state(state_path: &Path) -> HashMap<PathBuf, FileStamp> {
+    std::fs::read_to_string(state_path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let mtime = fields.next()?.parse().ok()?;
+            let size = fields.next()?.parse().ok()?;
+            Some((PathBuf::from(fields.next()?), (mtime, size)))
+        })
+        .collect()
+}
+
+fn save_watch_state(state_path: &Path, state: &HashMap<PathBuf, FileStamp>) -> Result<()> {
+    let mut content = String::new();
+    for (path, (mtime, size)) in state {
+        content.push_str(&format!("{}\t{}\t{}\n", mtime, size, path.display()));
+    }
+    std::fs::write(state_path, content)?;
+    Ok(())
+}
+
+/// Runs until interrupted. With `--json`, prints one compact envelope per
+/// processed file instead of the status lines.
+/// Read a `--name SECS` option; zero is refused so the poll loop can't spin.
+fn whole_seconds(args: &ParsedArgs, name: &str, default: u64) -> Result<Duration> {
+    let seconds = match args.value(name) {
+        Some(value) => value
+            .parse::<u64>()
+            .map_err(|_| anyhow!("{} expects whole seconds, got '{}'", name, value))?,
+        None => default,
+    };
+    if seconds == 0 {
+        return Err(anyhow!("{} must be at least 1 second", name));
+    }
+    Ok(Duration::from_secs(seconds))
+}
+
+fn watch(args: &[String], json: bool) -> Result<Report> {
+    let args = ParsedArgs::parse(
+        args,
+        &["--format", "--out", "--interval", "--debounce"],
+        &[],
+    )?;
+    let watch_dir = PathBuf::from(args.input()?);
+    if !watch_dir.is_dir() {
+        return Err(anyhow!("{} is not a directory", watch_dir.display()));
+    }
+    let format = ExportFormat::parse(args.value("--format").unwrap_or("txt"))?;
+    let out_dir = args
+        .value("--out")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| watch_dir.clone());
+    let interval = whole_seconds(&args, "--interval", 2)?;
+    let debounce = whole_seconds(&args, "--debounce", 3)?;
+
+    // Processed files survive restarts so nothing is extracted twice
+    std::fs::create_dir_all(&out_dir)?;
+    let state_path = out_dir.join(".chonker-watch");
+    let mut processed = load_watch_state(&state_path);
+
+    // Files that changed recently, waiting for writes to settle
+    let mut pending: HashMap<PathBuf, (FileStamp, Instant)> = HashMap::new();
+
+    let pdfium = load_pdfium()?;
+    log(
+        1,
+        format!(
+            "Watching {} -> {} ({} already processed, Ctrl+C to stop)",
+            watch_dir.display(),
+            out_dir.display(),
+            processed.len()
+        ),
+    );
+
+    loop {
+        let entries = std::fs::read_dir(&watch_dir)?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path
+                .extension()
+                .is_none_or(|ext| !ext.eq_ignore_ascii_case("pdf"))
+            {
+                continue;
+            }
+            let Some(stamp) = file_stamp(&path) else {
+                continue;
+            };
+            if processed.get(&path) == Some(&stamp) {
+                continue;
+            }
+
+            // Restart the debounce timer whenever the file is still changing
+            let settled = match pending.get(&path) {
+                Some((last, since)) if *last == stamp => since.elapsed() >= debounce,
+                _ => {
+                    pending.insert(path.clone(), (stamp, Instant::now()));
+                    false
+                }
+            };
+            if !settled {
+                continue;
+            }
+            pending.remove(&path);
+
+            let input = path.to_string_lossy().to_string();
+            let outcome =
+                extract_file(&pdfium, &input, None, &ExtractOptions::default()).and_then(|doc| {
+                    let target = output_in_dir(&out_dir, &input, format)?;
+                    std::fs::write(&target, export::render(&doc, format)?)?;
+                    Ok(target)
+                });
+            if json {
+                let (status, data) = match &outcome {
+                    Ok(target) => (
+                        "ok",
+                        serde_json::json!({ "input": input, "output": target }),
+                    ),
+                    Err(e) => (
+                        "failed",
+                        serde_json::json!({ "input": input, "error": e.to_string() }),
+                    ),
+                };
+                println!(
+                    "{}",
+                    serde_json::json!({
+                        "schema_version": JSON_SCHEMA_VERSION,
+                        "command": "watch",
+                        "status": status,
+                        "data": data,
+                    })
+                );
+            } else {
+                match outcome {
+                    Ok(target) => log(1, format!("ok   {} -> {}", input, target.display())),
+                    Err(e) => log(1, format!("FAIL {}: {}", input, e)),
+                }
+            }
+
+            // Failures are recorded too, so a broken file isn't retried until it changes
+            processed.insert(path, stamp);
+            save_watch_state(&state_path, &processed)?;
+        }
+
+        std::thread::sleep(interval);
+    }
+}
+
+/// Re-render a saved extraction in another format without touching the PDF.
+fn convert(args: &[String]) -> Result<Report> {
+    let args = ParsedArgs::parse(args, &["--from", "--to", "--out"], &[])?;
+    let input = args.input()?;
+    let from = match args.value("--from") {
+        Some(name) => ExportFormat::parse(name)?,
+        None => ExportFormat::from_path(Path::new(input))?,
+    };
+    let to = ExportFormat::parse(
+        args.value("--to")
+            .ok_or_else(|| anyhow!("convert needs --to FORMAT\n\n{}", USAGE))?,
+    )?;
+
+    let doc = export::parse(&std::fs::read_to_string(input)?, from, input)?;
+    let content = export::render(&doc, to)?;
+    deliver(input, args.value("--out"), to, &doc, content)
+}
+
+/// Load a saved `--format json` extraction, or extract a PDF on the fly.
+fn load_extraction(input: &str, page_spec: Option<&str>) -> Result<ExtractedDocument> {
+    let is_json = Path::new(input)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    if is_json {
+        let content = std::fs::read_to_string(input)?;
+        serde_json::from_str(&content).map_err(|e| anyhow!("{}: {}", input, e))
+    } else {
+        extract_file(
+            &load_pdfium()?,
+            input,
+            page_spec,
+            &ExtractOptions::default(),
+        )
+    }
+}
+
+/// Exits 0 when the extractions match and 1 when they differ, like diff(1).
+fn diff(args: &[String]) -> Result<Report> {
+    let args = ParsedArgs::parse(args, &["--pages", "--format"], &[])?;
+    let [old, new] = args.positional.as_slice() else {
+        return Err(anyhow!("diff expects exactly two inputs\n\n{}", USAGE));
+    };
+
+    let old = load_extraction(old, args.value("--pages"))?;
+    let new = load_extraction(new, args.value("--pages"))?;
+    let result = DocumentDiff::compute(&old, &new);
+
+    let text = match args.value("--format").unwrap_or("txt") {
+        "txt" | "text" => result.render_text(),
+        "json" => format!("{}\n", serde_json::to_string_pretty(&result)?),
+        other => {
+            return Err(anyhow!(
+                "Unknown diff format '{}' (expected txt or json)",
+                other
+            ))
+        }
+    };
+    Report::new(if result.is_empty() { 0 } else { 1 }, text, result)
+}
+
+// ============= VALIDATION =============
+//
+// Rules file (every key optional):
+//
+//     allow_empty_pages = false
+//     min_chars_per_page = 20
+//     min_pages = 1
+//     required_text = ["Total"]
+//     min_confidence = 0.6
+//     table_columns = 4
+//
+// `min_confidence` and `table_columns` check layout blocks, which only a JSON
+// export from the GUI carries; on any other input they fail rather than pass
+// unchecked.
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ValidationRules {
+    allow_empty_pages: bool,
+    min_chars_per_page: usize,
+    min_pages: usize,
+    required_text: Vec<String>,
+    /// Lowest placement confidence any block may have, 0.0 to 1.0
+    min_confidence: Option<f32>,
+    /// Column count every Table block must have
+    table_columns: Option<usize>,
+}
+
+impl ValidationRules {
+    fn load(path: Option<&str>) -> Result<Self> {
+        let Some(path) = path else {
+            return Ok(Self::default());
+        };
+        let content = std::fs::read_to_string(path)?;
+        toml::from_str(&content).map_err(|e| anyhow!("{}: {}", path, e))
+    }
+
+    /// Return one message per failed rule.
+    fn check(&self, doc: &ExtractedDocument) -> Vec<String> {
+        let mut failures = Vec::new();
+
+        if doc.pages.len() < self.min_pages {
+            failures.push(format!(
+                "expected at least {} pages, found {}",
+                self.min_pages,
+                doc.pages.len()
+            ));
+        }
+
+        for page in &doc.pages {
+            let chars: usize = page
+                .lines
+                .iter()
+                .map(|l| l.chars().filter(|c| !c.is_whitespace()).count())
+                .sum();
+            if chars == 0 {
+                if !self.allow_empty_pages {
+                    failures.push(format!("page {} is empty", page.page));
+                }
+            } else if chars < self.min_chars_per_page {
+                failures.push(format!(
+                    "page {} has {} characters (minimum {})",
+                    page.page, chars, self.min_chars_per_page
+                ));
+            }
+        }
+
+        for needle in &self.required_text {
+            let found = doc
+                .pages
+                .iter()
+                .any(|p| p.lines.iter().any(|l| l.contains(needle.as_str())));
+            if !found {
+                failures.push(format!("required text '{}' not found", needle));
+            }
+        }
+
+        let has_blocks = doc.pages.iter().any(|p| !p.regions.is_empty());
+        if let Some(minimum) = self.min_confidence {
+            if !has_blocks {
+                failures.push(
+                    "min_confidence needs layout blocks; validate a JSON export from the GUI"
+                        .to_string(),
+                );
+            }
+            for page in &doc.pages {
+                let low: Vec<f32> = page
+                    .regions
+                    .iter()
+                    .map(|r| r.confidence)
+                    .filter(|&c| c < minimum)
+                    .collect();
+                if let Some(lowest) = low.iter().copied().reduce(f32::min) {
+                    failures.push(format!(
+                        "page {} has {} block(s) below confidence {:.2} (lowest {:.2})",
+                        page.page,
+                        low.len(),
+                        minimum,
+                        lowest
+                    ));
+                }
+            }
+        }
+
+        if let Some(columns) = self.table_columns {
+            if !has_blocks {
+                failures.push(
+                    "table_columns needs layout blocks; validate a JSON export from the GUI"
+                        .to_string(),
+                );
+            }
+            for page in &doc.pages {
+                for region in &page.regions {
+                    let Some(table) = &region.table else {
+                        continue;
+                    };
+                    if table.columns() != columns {
+                        failures.push(format!(
+                            "page {} table R{} has {} columns (expected {})",
+                            page.page,
+                            region.id,
+                            table.columns(),
+                            columns
+                        ));
+                    }
+                }
+            }
+        }
+
+        failures
+    }
+}
+
+/// Exit codes: 0 all rules pass, 1 a rule failed, 2 the input or rules
+/// could not be loaded.
+fn validate(args: &[String]) -> Result<Report> {
+    let args = ParsedArgs::parse(args, &["--rules", "--pages"], &[])?;
+    let input = args.input()?;
+    let rules = ValidationRules::load(args.value("--rules"))?;
+    let doc = load_extraction(input, args.value("--pages"))?;
+
+    let failures = rules.check(&doc);
+    let text = if failures.is_empty() {
+        format!("PASS {} ({} pages)\n", input, doc.pages.len())
+    } else {
+        failures
+            .iter()
+            .map(|failure| format!("FAIL {}: {}\n", input, failure))
+            .collect()
+    };
+    let data = serde_json::json!({
+        "input": input,
+        "pages": doc.pages.len(),
+        "passed": failures.is_empty(),
+        "failures": failures,
+    });
+    Report::new(if failures.is_empty() { 0 } else { 1 }, text, data)
+}
+
+// ============= METADATA =============
+
+#[derive(Serialize)]
+struct PageInfo {
+    page: usize,
+    width_pt: f32,
+    height_pt: f32,
+    has_text_layer: bool,
+}
+
+/// Triage info for deciding how to process a document: native text vs scans,
+/// encryption, fonts.
+fn metadata(args: &[String]) -> Result<Report> {
+    let args = ParsedArgs::parse(args, &[], &[])?;
+    let input = args.input()?;
+    let pdfium = load_pdfium()?;
+    let document = pdfium.load_pdf_from_file(input, None)?;
+
+    let tags = [
+        ("title", "Title", PdfDocumentMetadataTagType::Title),
+        ("author", "Author", PdfDocumentMetadataTagType::Author),
+        ("subject", "Subject", PdfDocumentMetadataTagType::Subject),
+        ("keywords", "Keywords", PdfDocumentMetadataTagType::Keywords),
+        ("creator", "Creator", PdfDocumentMetadataTagType::Creator),
+        ("producer", "Producer", PdfDocumentMetadataTagType::Producer),
+        (
+            "created",
+            "Created",
+            PdfDocumentMetadataTagType::CreationDate,
+        ),
+        (
+            "modified",
+            "Modified",
+            PdfDocumentMetadataTagType::ModificationDate,
+        ),
+    ];
+    let info: Vec<(&str, &str, Option<String>)> = tags
+        .into_iter()
+        .map(|(key, label, tag)| {
+            let value = document.metadata().get(tag).map(|t| t.value().to_string());
+            (key, label, value)
+        })
+        .collect();
+
+    let encryption = match document.permissions().security_handler_revision() {
+        Ok(PdfSecurityHandlerRevision::Unprotected) => None,
+        Ok(revision) => Some(format!("{:?}", revision)),
+        Err(e) => Some(format!("unknown ({})", e)),
+    };
+
+    let mut pages = Vec::new();
+    let mut fonts = BTreeSet::new();
+    for (index, page) in document.pages().iter().enumerate() {
+        for object in page.objects().iter() {
+            if let Some(text) = object.as_text_object() {
+                fonts.insert(text.font().name());
+            }
+        }
+        pages.push(PageInfo {
+            page: index + 1,
+            width_pt: page.width().value,
+            height_pt: page.height().value,
+            has_text_layer: !page.text()?.all().trim().is_empty(),
+        });
+    }
+
+    let mut text = format!("File:        {}\n", input);
+    text.push_str(&format!("Version:     {:?}\n", document.version()));
+    for (_, label, value) in &info {
+        if let Some(value) = value {
+            text.push_str(&format!("{:<12} {}\n", format!("{}:", label), value));
+        }
+    }
+    text.push_str(&format!(
+        "Encryption:  {}\n",
+        encryption.as_deref().unwrap_or("none")
+    ));
+    text.push_str(&format!("Pages:       {}\n", pages.len()));
+    let font_list: Vec<&str> = fonts.iter().map(String::as_str).collect();
+    text.push_str(&format!("Fonts:       {}\n", font_list.join(", ")));
+    let scanned: Vec<String> = pages
+        .iter()
+        .filter(|p| !p.has_text_layer)
+        .map(|p| p.page.to_string())
+        .collect();
+    if !scanned.is_empty() {
+        text.push_str(&format!("No text:     pages {}\n", scanned.join(", ")));
+    }
+    text.push_str("\nPage  Size (pt)          Text layer\n");
+    for page in &pages {
+        text.push_str(&format!(
+            "{:<5} {:<18} {}\n",
+            page.page,
+            format!("{:.0} x {:.0}", page.width_pt, page.height_pt),
+            if page.has_text_layer { "yes" } else { "no" }
+        ));
+    }
+
+    let data = serde_json::json!({
+        "file": input,
+        "version": format!("{:?}", document.version()),
+        "info": info
+            .iter()
+            .map(|(key, _, value)| (key.to_string(), serde_json::json!(value)))
+            .collect::<serde_json::Map<_, _>>(),
+        "encryption": encryption,
+        "fonts": fonts,
+        "pages": pages,
+    });
+    Report::new(0, text, data)
+}
+
+// ============= REDACTION =============
+//
+// Region files are a JSON list of boxes in PDF points (origin bottom-left):
+//
+//     [{ "page": 1, "left": 72, "bottom": 600, "right": 200, "top": 614 }]
+//
+// Text objects touching a box are removed from the page, not just covered,
+// so a redacted value can't be copied back out. pdfium can't split a text
+// object, so neighbouring words in the same object are removed as well.
+// Images are only covered by the black box. Text inside form XObjects and
+// annotation appearances can't be removed this way, so a box touching
+// either fails the whole run rather than leaving the text extractable.
+// A --pattern that matches nothing still writes OUT.pdf but exits 1.
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct RedactRegion {
+    /// 1-based page number
+    page: usize,
+    left: f32,
+    bottom: f32,
+    right: f32,
+    top: f32,
+}
+
+/// Merge the boxes of consecutive characters into one box per text line.
+fn merge_char_boxes(page: usize, boxes: &[[f32; 4]]) -> Vec<RedactRegion> {
+    let mut merged: Vec<RedactRegion> = Vec::new();
+    for &[left, bottom, right, top] in boxes {
+        match merged.last_mut() {
+            // Same line if the vertical centres are within half a line height
+            Some(last)
+                if ((last.bottom + last.top) - (bottom + top)).abs() / 2.0
+                    < (top - bottom).max(1.0) / 2.0 =>
+            {
+                last.left = last.left.min(left);
+                last.bottom = last.bottom.min(bottom);
+                last.right = last.right.max(right);
+                last.top = last.top.max(top);
+            }
+            _ => merged.push(RedactRegion {
+                page,
+                left,
+                bottom,
+                right,
+                top,
+            }),
+        }
+    }
+    merged
+}
+
+/// Find every match of `pattern` in a page's text layer and map it to boxes.
+/// Fails if a visible character in a match has no bounds, since its box
+/// would be missing and the character left in place.
+fn find_pattern_regions(
+    page: &PdfPage,
+    number: usize,
+    pattern: &Regex,
+) -> Result<Vec<RedactRegion>> {
+    let text = page.text()?;
+    let chars: Vec<(char, Option<[f32; 4]>)> = text
+        .chars()
+        .iter()
+        .map(|c| {
+            let bounds = c.tight_bounds().ok().map(|b| {
+                [
+                    b.left().value,
+                    b.bottom().value,
+                    b.right().value,
+                    b.top().value,
+                ]
+            });
+            (c.unicode_char().unwrap_or('\u{fffd}'), bounds)
+        })
+        .collect();
+    let content: String = chars.iter().map(|(c, _)| *c).collect();
+
+    let mut regions = Vec::new();
+    for found in pattern.find_iter(&content) {
+        let start = content[..found.start()].chars().count();
+        let end = start + found.as_str().chars().count();
+        let mut boxes = Vec::new();
+        for &(c, bounds) in &chars[start..end] {
+            match bounds {
+                Some(bounds) => boxes.push(bounds),
+                // Spaces pdfium generated between words have no glyph to leak
+                None if c.is_whitespace() => {}
+                None => {
+                    return Err(anyhow!(
+                        "Page {}: can't locate {:?} in the match {:?}, so it can't be \
+                         redacted. Nothing was written.",
+                        number,
+                        c,
+                        found.as_str()
+                    ))
+                }
+            }
+        }
+        regions.extend(merge_char_boxes(number, &boxes));
+    }
+    Ok(regions)
+}
+
+fn rects_overlap(a: &PdfRect, b: &PdfRect) -> bool {
+    a.left().value < b.right().value
+        && b.left().value < a.right().value
+        && a.bottom().value < b.top().value
+        && b.bottom().value < a.top().value
+}
+
+/// Whether an object is text, or a form XObject with text somewhere inside.
+fn contains_text(object: &PdfPageObject) -> bool {
+    match object.as_x_object_form_object() {
+        Some(form) => form.iter().any(|child| contains_text(&child)),
+        None => object.object_type() == PdfPageObjectType::Text,
+    }
+}
+
+fn redact(args: &[String]) -> Result<Report> {
+    let args = ParsedArgs::parse(args, &["--pattern", "--regions", "--out"], &[])?;
+    let input = args.input()?;
+    let out = args
+        .value("--out")
+        .ok_or_else(|| anyhow!("redact needs --out OUT.pdf\n\n{}", USAGE))?;
+    if args.value("--pattern").is_none() && args.value("--regions").is_none() {
+        return Err(anyhow!("redact needs --pattern and/or --regions"));
+    }
+    let pattern = args.value("--pattern").map(Regex::new).transpose()?;
+
+    let mut regions: Vec<RedactRegion> = match args.value("--regions") {
+        Some(path) => serde_json::from_str(&std::fs::read_to_string(path)?)
+            .map_err(|e| anyhow!("{}: {}", path, e))?,
+        None => Vec::new(),
+    };
+
+    let pdfium = load_pdfium()?;
+    let document = pdfium.load_pdf_from_file(input, None)?;
+    let total_pages = document.pages().len() as usize;
+    if let Some(region) = regions.iter().find(|r| r.page == 0 || r.page > total_pages) {
+        return Err(anyhow!(
+            "Region on page {} is outside the document ({} pages)",
+            region.page,
+            total_pages
+        ));
+    }
+
+    let mut pattern_regions = 0;
+    if let Some(pattern) = &pattern {
+        for (index, page) in document.pages().iter().enumerate() {
+            let found = find_pattern_regions(&page, index + 1, pattern)?;
+            pattern_regions += found.len();
+            regions.extend(found);
+        }
+    }
+
+    let mut removed_objects = 0;
+    for index in 0..total_pages {
+        let boxes: Vec<PdfRect> = regions
+            .iter()
+            .filter(|r| r.page == index + 1)
+            .map(|r| PdfRect::new_from_values(r.bottom, r.left, r.top, r.right))
+            .collect();
+        if boxes.is_empty() {
+            continue;
+        }
+
+        let mut page = document.pages().get(index as u16)?;
+        for annotation in page.annotations().iter() {
+            let Ok(bounds) = annotation.bounds() else {
+                continue;
+            };
+            if boxes.iter().any(|b| rects_overlap(b, &bounds)) {
+                return Err(anyhow!(
+                    "Page {}: a redaction box overlaps a {:?} annotation, whose text can't be \
+                     removed; flatten the PDF first. Nothing was written.",
+                    index + 1,
+                    annotation.annotation_type()
+                ));
+            }
+        }
+
+        // Walk backwards so removals don't shift the indices still to visit
+        for object_index in (0..page.objects().len()).rev() {
+            let overlaps = {
+                let object = page.objects().get(object_index)?;
+                if !boxes.iter().any(|b| object.does_overlap_rect(b)) || !contains_text(&object) {
+                    false
+                } else if object.object_type() == PdfPageObjectType::Text {
+                    true
+                } else {
+                    return Err(anyhow!(
+                        "Page {}: a redaction box overlaps text inside a form XObject, which \
+                         can't be removed; flatten the PDF first. Nothing was written.",
+                        index + 1
+                    ));
+                }
+            };
+            if overlaps {
+                page.objects_mut().remove_object_at_index(object_index)?;
+                removed_objects += 1;
+            }
+        }
+        for rect in boxes {
+            page.objects_mut()
+                .create_path_object_rect(rect, None, None, Some(PdfColor::BLACK))?;
+        }
+    }
+    document.save_to_file(out)?;
+    log(1, format!("Wrote {}", out));
+
+    let text = format!(
+        "Redacted {} region(s) on {} page(s), removed {} text object(s)\n",
+        regions.len(),
+        regions
+            .iter()
+            .map(|r| r.page)
+            .collect::<BTreeSet<_>>()
+            .len(),
+        removed_objects
+    );
+    let data = serde_json::json!({
+        "input": input,
+        "output": out,
+        "removed_text_objects": removed_objects,
+        "regions": regions,
+    });
+    // A pattern that matches nothing usually means a typo, not a clean document
+    let unmatched = pattern.as_ref().filter(|_| pattern_regions == 0);
+    let mut report = Report::new(if unmatched.is_some() { 1 } else { 0 }, text, data)?;
+    if let Some(pattern) = unmatched {
+        report
+            .warnings
+            .push(format!("--pattern {:?} matched nothing", pattern.as_str()));
+    }
+    Ok(report)
+}
+
+// ============= SPLIT & MERGE =============
+
+/// Write every `--every` pages of the input to `<stem>-partNN.pdf`.
+fn split(args: &[String]) -> Result<Report> {
+    let args = ParsedArgs::parse(args, &["--every", "--out"], &[])?;
+    let input = args.input()?;
+    let every = args
+        .value("--every")
+        .ok_or_else(|| anyhow!("split needs --every N\n\n{}", USAGE))?
+        .parse::<u16>()?;
+    if every == 0 {
+        return Err(anyhow!("--every must be at least 1"));
+    }
+
+    let pdfium = load_pdfium()?;
+    let source = pdfium.load_pdf_from_file(input, None)?;
+    let total_pages = source.pages().len();
+    let out_dir = match args.value("--out") {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(input)
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default(),
+    };
+    if !out_dir.as_os_str().is_empty() {
+        std::fs::create_dir_all(&out_dir)?;
+    }
+    let stem = Path::new(input)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "output".to_string());
+
+    let mut parts = Vec::new();
+    let mut text = String::new();
+    for (part, first) in (0..total_pages).step_by(every as usize).enumerate() {
+        let last = first.saturating_add(every - 1).min(total_pages - 1);
+        let mut piece = pdfium.create_new_pdf()?;
+        piece
+            .pages_mut()
+            .copy_page_range_from_document(&source, first..=last, 0)?;
+
+        let target = out_dir.join(format!("{}-part{:02}.pdf", stem, part + 1));
+        piece.save_to_file(&target)?;
+        text.push_str(&format!(
+            "pages {}-{} -> {}\n",
+            first + 1,
+            last + 1,
+            target.display()
+        ));
+        parts.push(serde_json::json!({
+            "output": target,
+            "first_page": first + 1,
+            "last_page": last + 1,
+        }));
+    }
+
+    let data = serde_json::json!({ "input": input, "pages": total_pages, "parts": parts });
+    Report::new(0, text, data)
+}
+
+/// Concatenate the inputs, in order, into one PDF.
+fn merge(args: &[String]) -> Result<Report> {
+    let args = ParsedArgs::parse(args, &["-o", "--out"], &[])?;
+    let out = args
+        .value("-o")
+        .or(args.value("--out"))
+        .ok_or_else(|| anyhow!("merge needs -o OUT.pdf\n\n{}", USAGE))?;
+    if args.positional.len() < 2 {
+        return Err(anyhow!("merge needs at least two inputs\n\n{}", USAGE));
+    }
+
+    let pdfium = load_pdfium()?;
+    let mut merged = pdfium.create_new_pdf()?;
+    let mut inputs = Vec::new();
+    for input in &args.positional {
+        let source = pdfium
+            .load_pdf_from_file(input, None)
+            .map_err(|e| anyhow!("{}: {}", input, e))?;
+        inputs.push(serde_json::json!({ "input": input, "pages": source.pages().len() }));
+        merged.pages_mut().append(&source)?;
+    }
+    merged.save_to_file(out)?;
+
+    let total_pages = merged.pages().len();
+    let text = format!(
+        "Merged {} files ({} pages) -> {}\n",
+        inputs.len(),
+        total_pages,
+        out
+    );
+    let data = serde_json::json!({ "output": out, "pages": total_pages, "inputs": inputs });
+    Report::new(0, text, data)
+}
+
+// ============= BENCHMARK =============
+//
+// Times the stages the CLI actually runs. It has no vision fusion step (that
+// only exists in the GUI's Smart Layout path), so there is no fuse stage.
+
+const BENCH_STAGES: &[&str] = &["load", "render", "extract", "export"];
+
+// Roughly what the TUI renders a page at on a large terminal
+const BENCH_RENDER_WIDTH: i32 = 1600;
+
+#[derive(Serialize)]
+struct StageStats {
+    stage: &'static str,
+    mean_ms: f64,
+    min_ms: f64,
+    max_ms: f64,
+}
+
+impl StageStats {
+    fn from_samples(stage: &'static str, samples: &[Duration]) -> Self {
+        let ms: Vec<f64> = samples.iter().map(|d| d.as_secs_f64() * 1000.0).collect();
+        Self {
+            stage,
+            mean_ms: ms.iter().sum::<f64>() / ms.len().max(1) as f64,
+            min_ms: ms.iter().copied().fold(f64::INFINITY, f64::min),
+            max_ms: ms.iter().copied().fold(0.0, f64::max),
+        }
+    }
+}
+
+/// Peak resident set size in KiB. Only available on Linux, via /proc.
+fn peak_rss_kib() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find_map(|l| l.strip_prefix("VmHWM:"))?;
+    line.trim().trim_end_matches("kB").trim().parse().ok()
+}
+
+fn bench(args: &[String]) -> Result<Report> {
+    let args = ParsedArgs::parse(args, &["--runs", "--pages", "--format"], &[])?;
+    if args.positional.is_empty() {
+        return Err(anyhow!("Missing input file\n\n{}", USAGE));
+    }
+    let runs = args.value("--runs").unwrap_or("5").parse::<usize>()?.max(1);
+    let format = ExportFormat::parse(args.value("--format").unwrap_or("txt"))?;
+    let pdfium = load_pdfium()?;
+    let progress = Progress::new(runs * args.positional.len(), "runs");
+
+    let mut text = String::new();
+    let mut files = Vec::new();
+    for input in &args.positional {
+        let mut samples = vec![Vec::with_capacity(runs); BENCH_STAGES.len()];
+        let mut page_count = 0;
+
+        for _ in 0..runs {
+            let started = Instant::now();
+            let document = pdfium.load_pdf_from_file(input, None)?;
+            samples[0].push(started.elapsed());
+
+            let total_pages = document.pages().len() as usize;
+            let pages = match args.value("--pages") {
+                Some(spec) => export::parse_page_range(spec, total_pages)?,
+                None => (0..total_pages).collect(),
+            };
+            page_count = pages.len();
+
+            let started = Instant::now();
+            let config = PdfRenderConfig::new().set_target_width(BENCH_RENDER_WIDTH);
+            for &page in &pages {
+                document
+                    .pages()
+                    .get(page as u16)?
+                    .render_with_config(&config)?;
+            }
+            samples[1].push(started.elapsed());
+
+            let started = Instant::now();
+            let extracted = extract_document(
+                &document,
+                input,
+                args.value("--pages"),
+                &ExtractOptions::default(),
+                false,
+            )?;
+            samples[2].push(started.elapsed());
+
+            let started = Instant::now();
+            export::render(&extracted, format)?;
+            samples[3].push(started.elapsed());
+
+            progress.inc();
+        }
+
+        let stages: Vec<StageStats> = BENCH_STAGES
+            .iter()
+            .zip(&samples)
+            .map(|(stage, samples)| StageStats::from_samples(*stage, samples))
+            .collect();
+
+        text.push_str(&format!(
+            "{} ({} pages, {} runs)\n  {:<8} {:>10} {:>10} {:>10}\n",
+            input, page_count, runs, "stage", "mean ms", "min ms", "max ms"
+        ));
+        for s in &stages {
+            text.push_str(&format!(
+                "  {:<8} {:>10.2} {:>10.2} {:>10.2}\n",
+                s.stage, s.mean_ms, s.min_ms, s.max_ms
+            ));
+        }
+        files.push(serde_json::json!({
+            "input": input,
+            "pages": page_count,
+            "stages": stages,
+        }));
+    }
+    progress.finish();
+
+    let peak_rss = peak_rss_kib();
+    match peak_rss {
+        Some(kib) => text.push_str(&format!("peak RSS: {:.1} MiB\n", kib as f64 / 1024.0)),
+        None => text.push_str("peak RSS: unavailable on this platform\n"),
+    }
+    let data = serde_json::json!({
+        "runs": runs,
+        "files": files,
+        "peak_rss_kib": peak_rss,
+    });
+    Report::new(0, text, data)
+}
+
+// ============= PIPELINES =============
+//
+// A pipeline file names the inputs and the stages to run on each of them:
+//
+//     input = "reports/*.pdf"
+//
+//     [[stage]]
+//     type = "extract"
+//     pages = "1-20"
+//
+//     [[stage]]
+//     type = "export"
+//     format = "md"
+//     out = "out/"
+//
+// Relative paths are resolved against the pipeline file's directory. Export
+// stages must not share both a format and an `out`. The enhance and
+// table-clean stages aren't implemented and are rejected by name.
+
+#[derive(Deserialize)]
+struct PipelineSpec {
+    input: String,
+    #[serde(rename = "stage", default)]
+    stages: Vec<PipelineStage>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case", deny_unknown_fields)]
+enum PipelineStage {
+    Extract { pages: Option<String> },
+    Export { format: String, out: Option<String> },
+}
+
+impl PipelineSpec {
+    fn load(path: &Path) -> Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+            .map_err(|e| anyhow!("{}: {}", path.display(), e))
+    }
+
+    fn parse(content: &str) -> Result<Self> {
+        // Stages from the pipeline design that don't run yet get a clearer
+        // error than serde's "unknown variant"
+        let raw: toml::Table = toml::from_str(content)?;
+        let stage_types = raw
+            .get("stage")
+            .and_then(toml::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|stage| stage.get("type").and_then(toml::Value::as_str));
+        for kind in stage_types {
+            if kind == "enhance" || kind == "table-clean" {
+                return Err(anyhow!(
+                    "{} stages aren't supported yet; a pipeline can only extract and export",
+                    kind
+                ));
+            }
+        }
+        let spec: PipelineSpec = toml::from_str(content)?;
+
+        match spec.stages.first() {
+            Some(PipelineStage::Extract { .. }) => {}
+            _ => return Err(anyhow!("the first stage must be extract")),
+        }
+        let extract_stages = spec
+            .stages
+            .iter()
+            .filter(|s| matches!(s, PipelineStage::Extract { .. }))
+            .count();
+        if extract_stages > 1 {
+            return Err(anyhow!("only one extract stage is allowed"));
+        }
+        if spec.stages.len() < 2 {
+            return Err(anyhow!("add at least one export stage"));
+        }
+
+        // Two exports to the same directory in the same format would write the same files
+        let mut targets = BTreeSet::new();
+        for stage in &spec.stages {
+            if let PipelineStage::Export { format, out } = stage {
+                let extension = ExportFormat::parse(format)?.extension();
+                if !targets.insert((out.as_ref().map(PathBuf::from), extension)) {
+                    return Err(anyhow!(
+                        "two export stages write .{} files to {}; give one a different out",
+                        extension,
+                        out.as_deref().unwrap_or("the input directory")
+                    ));
+                }
+            }
+        }
+        Ok(spec)
+    }
+}
+
+fn run_pipeline(args: &[String]) -> Result<Report> {
+    let args = ParsedArgs::parse(args, &[], &[])?;
+    let spec_path = PathBuf::from(args.input()?);
+    let spec = PipelineSpec::load(&spec_path)?;
+    let base = spec_path.parent().unwrap_or(Path::new("."));
+
+    let pattern = base.join(&spec.input);
+    let inputs: Vec<String> = glob::glob(&pattern.to_string_lossy())?
+        .filter_map(|entry| entry.ok())
+        .filter(|path| path.is_file())
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+    if inputs.is_empty() {
+        return Err(anyhow!("No files match '{}'", pattern.display()));
+    }
+
+    // Output paths per export stage with an `out` directory, checked for clashes up front
+    let stage_targets = spec
+        .stages
+        .iter()
+        .map(|stage| match stage {
+            PipelineStage::Export {
+                format,
+                out: Some(dir),
+            } => mirrored_outputs(
+                &base.join(dir),
+                &pattern.to_string_lossy(),
+                &inputs,
+                ExportFormat::parse(format)?,
+            )
+            .map(Some),
+            _ => Ok(None),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let pdfium = load_pdfium()?;
+    let progress = Progress::new(inputs.len(), "files");
+    let mut files = Vec::with_capacity(inputs.len());
+    let mut failed = 0;
+    for (index, input) in inputs.iter().enumerate() {
+        let mut outputs = Vec::new();
+        let stages = spec.stages.iter().zip(&stage_targets);
+        let outcome = stages.try_fold(None, |doc, (stage, targets)| match stage {
+            PipelineStage::Extract { pages } => {
+                extract_file(&pdfium, input, pages.as_deref(), &ExtractOptions::default()).map(Some)
+            }
+            PipelineStage::Export { format, .. } => {
+                let format = ExportFormat::parse(format)?;
+                let target = match targets {
+                    Some(targets) => targets[index].clone(),
+                    None => Path::new(input).with_extension(format.extension()),
+                };
+                // load() guarantees extract runs first
+                let extracted = doc.as_ref().expect("extract stage runs first");
+                write_creating_dirs(&target, &export::render(extracted, format)?)?;
+                progress.log(2, format!("ok   {} -> {}", input, target.display()));
+                outputs.push(target.display().to_string());
+                Ok(doc)
+            }
+        });
+
+        progress.inc();
+        let error = outcome.err().map(|e| {
+            progress.log(1, format!("FAIL {}: {}", input, e));
+            failed += 1;
+            e.to_string()
+        });
+        files.push(serde_json::json!({ "input": input, "outputs": outputs, "error": error }));
+    }
+
+    progress.finish();
+    log(
+        1,
+        format!(
+            "Pipeline finished: {} succeeded, {} failed",
+            inputs.len() - failed,
+            failed
+        ),
+    );
+    let data = serde_json::json!({
+        "total": inputs.len(),
+        "succeeded": inputs.len() - failed,
+        "failed": failed,
+        "files": files,
+    });
+    Report::new(if failed > 0 { 1 } else { 0 }, String::new(), data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mirrored_outputs_keep_same_named_files_apart() {
+        assert_eq!(glob_base("docs/**/*.pdf"), PathBuf::from("docs"));
+        assert_eq!(glob_base("*.pdf"), PathBuf::new());
+
+        let inputs = vec!["docs/a/report.pdf".to_string(), "docs/b/report.pdf".to_string()];
+        let targets =
+            mirrored_outputs(Path::new("out"), "docs/**/*.pdf", &inputs, ExportFormat::Json)
+                .unwrap();
+        assert_eq!(
+            targets,
+            vec![
+                PathBuf::from("out/a/report.json"),
+                PathBuf::from("out/b/report.json")
+            ]
+        );
+
+        let clash = vec!["docs/x.pdf".to_string(), "docs/x.PDF".to_string()];
+        assert!(mirrored_outputs(Path::new("out"), "docs/*", &clash, ExportFormat::Text).is_err());
+    }
+
+    #[test]
+    fn test_parsed_args() {
+        let args: Vec<String> = ["in.pdf", "--format", "json", "--pages=2-4"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let parsed = ParsedArgs::parse(&args, &["--format", "--pages"], &[]).unwrap();
+        assert_eq!(parsed.input().unwrap(), "in.pdf");
+        assert_eq!(parsed.value("--format"), Some("json"));
+        assert_eq!(parsed.value("--pages"), Some("2-4"));
+        assert!(ParsedArgs::parse(&args[..2], &[], &[]).is_err());
+    }
+
+    #[test]
+    fn test_whole_seconds() {
+        let args: Vec<String> = ["dir", "--interval", "0", "--debounce", "5"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let parsed = ParsedArgs::parse(&args, &["--interval", "--debounce"], &[]).unwrap();
+        assert!(whole_seconds(&parsed, "--interval", 2).is_err());
+        assert_eq!(whole_seconds(&parsed, "--debounce", 3).unwrap(), Duration::from_secs(5));
+        assert_eq!(whole_seconds(&parsed, "--missing", 3).unwrap(), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_extract_options() {
+        let parse = |flags: &[&str]| {
+            let args: Vec<String> =
+                ["in.pdf"].iter().chain(flags).map(|s| s.to_string()).collect();
+            parse_extract_options(&ParsedArgs::parse(&args, &["--rotate", "--crop"], &[]).unwrap())
+        };
+        assert_eq!(parse(&["--rotate", "270"]).unwrap().rotate, 270);
+        assert_eq!(parse(&[]).unwrap().rotate, 0);
+        assert!(parse(&["--rotate", "45"]).is_err());
+        assert!(parse(&["--rotate", "-90"]).is_err());
+        assert!(parse(&["--rotate", "360"]).is_err());
+
+        assert_eq!(
+            parse(&["--crop", "0, 0, 300,400"]).unwrap().crop,
+            Some([0.0, 0.0, 300.0, 400.0])
+        );
+        assert!(parse(&["--crop", "300,0,0,400"]).is_err());
+        assert!(parse(&["--crop", "0,0,300"]).is_err());
+    }
+
+    #[test]
+    fn test_pipeline_stage_parsing() {
+        let spec = PipelineSpec::parse(
+            r#"
+            input = "*.pdf"
+
+            [[stage]]
+            type = "extract"
+            pages = "1-2"
+
+            [[stage]]
+            type = "export"
+            format = "md"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(spec.stages.len(), 2);
+        assert!(matches!(&spec.stages[0], PipelineStage::Extract { pages: Some(p) } if p == "1-2"));
+
+        let unsupported = PipelineSpec::parse(
+            r#"
+            input = "*.pdf"
+            [[stage]]
+            type = "enhance"
+            "#,
+        );
+        assert!(unsupported.unwrap_err().to_string().contains("enhance"));
+
+        let clash = PipelineSpec::parse(
+            r#"
+            input = "*.pdf"
+            [[stage]]
+            type = "extract"
+            [[stage]]
+            type = "export"
+            format = "md"
+            out = "out"
+            [[stage]]
+            type = "export"
+            format = "markdown"
+            out = "out/"
+            "#,
+        );
+        assert!(clash.is_err());
+    }
+
+    #[test]
+    fn test_validation_rules() {
+        let doc = ExtractedDocument {
+            source: "test".to_string(),
+            pages: vec![
+                ExtractedPage {
+                    page: 1,
+                    width: 10,
+                    height: 1,
+                    lines: vec!["Total  42".to_string()],
+                    regions: Vec::new(),
+                },
+                ExtractedPage {
+                    page: 2,
+                    width: 10,
+                    height: 1,
+                    lines: vec!["   ".to_string()],
+                    regions: Vec::new(),
+                },
+            ],
+        };
+
+        let rules: ValidationRules = toml::from_str("required_text = [\"Total\"]").unwrap();
+        assert_eq!(rules.check(&doc), vec!["page 2 is empty"]);
+
+        let rules: ValidationRules =
+            toml::from_str("allow_empty_pages = true\nmin_chars_per_page = 10").unwrap();
+        assert_eq!(
+            rules.check(&doc),
+            vec!["page 1 has 7 characters (minimum 10)"]
+        );
+
+        assert!(toml::from_str::<ValidationRules>("max_pages = 9").is_err());
+        let rules: ValidationRules = toml::from_str("min_confidence = 0.9").unwrap();
+        assert_eq!(rules.check(&doc).len(), 2, "no blocks to check counts as a failure");
+    }
+
+    #[test]
+    fn test_confidence_and_table_rules() {
+        let region = |id: usize, confidence: f32, columns: Option<usize>| export::ExtractedRegion {
+            id,
+            x: 0,
+            y: 0,
+            width: 10,
+            height: 1,
+            block_type: if columns.is_some() { "Table" } else { "Paragraph" }.to_string(),
+            confidence,
+            text: String::new(),
+            table: columns.map(|n| export::ExtractedTable {
+                cells: vec![vec![String::new(); n]; 2],
+                header_rows: 1,
+            }),
+        };
+        let mut page = ExtractedPage::from_lines(1, vec!["Total  42".to_string()]);
+        page.regions = vec![region(1, 0.95, None), region(2, 0.4, Some(3)), region(3, 0.5, None)];
+        let doc = ExtractedDocument {
+            source: "test".to_string(),
+            pages: vec![page],
+        };
+
+        let rules: ValidationRules = toml::from_str("min_confidence = 0.3").unwrap();
+        assert!(rules.check(&doc).is_empty());
+        let rules: ValidationRules = toml::from_str("min_confidence = 0.6").unwrap();
+        assert_eq!(
+            rules.check(&doc),
+            vec!["page 1 has 2 block(s) below confidence 0.60 (lowest 0.40)"]
+        );
+
+        let rules: ValidationRules = toml::from_str("table_columns = 3").unwrap();
+        assert!(rules.check(&doc).is_empty());
+        let rules: ValidationRules = toml::from_str("table_columns = 4").unwrap();
+        assert_eq!(
+            rules.check(&doc),
+            vec!["page 1 table R2 has 3 columns (expected 4)"]
+        );
+    }
+
+    #[test]
+    fn test_merge_char_boxes() {
+        let boxes = [
+            [10.0, 100.0, 16.0, 112.0],
+            [16.0, 100.5, 22.0, 112.0],
+            // Match wraps onto the next line
+            [10.0, 86.0, 16.0, 98.0],
+        ];
+        let merged = merge_char_boxes(3, &boxes);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(
+            merged[0],
+            RedactRegion {
+                page: 3,
+                left: 10.0,
+                bottom: 100.0,
+                right: 22.0,
+                top: 112.0
+            }
+        );
+        assert_eq!(merged[1].bottom, 86.0);
+    }
+
+    #[test]
+    fn test_redact_removes_matched_text() {
+        // Needs the pdfium library, like every command that opens a PDF
+        let Ok(pdfium) = load_pdfium() else {
+            eprintln!("skipping: pdfium library not found");
+            return;
+        };
+        let dir = std::env::temp_dir().join(format!("chonker-redact-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("in.pdf");
+        let output = dir.join("out.pdf");
+        {
+            let mut document = pdfium.create_new_pdf().unwrap();
+            let font = document.fonts_mut().helvetica();
+            let mut page = document
+                .pages_mut()
+                .create_page_at_end(PdfPagePaperSize::a4())
+                .unwrap();
+            for (y, line) in [(700.0, "Account 4111-2222"), (600.0, "Keep this line")] {
+                page.objects_mut()
+                    .create_text_object(
+                        PdfPoints::new(72.0),
+                        PdfPoints::new(y),
+                        line,
+                        font,
+                        PdfPoints::new(12.0),
+                    )
+                    .unwrap();
+            }
+            document.save_to_file(&input).unwrap();
+        }
+
+        let args = |pattern: &str| -> Vec<String> {
+            vec![
+                input.display().to_string(),
+                "--pattern".to_string(),
+                pattern.to_string(),
+                "--out".to_string(),
+                output.display().to_string(),
+            ]
+        };
+        let report = redact(&args(r"\d{4}-\d{4}")).unwrap();
+        assert_eq!(report.code, 0);
+        assert_eq!(report.data["removed_text_objects"], 1);
+
+        let redacted = pdfium.load_pdf_from_file(&output, None).unwrap();
+        let text = redacted.pages().get(0).unwrap().text().unwrap().all();
+        assert!(!text.contains("4111"));
+        assert!(text.contains("Keep this line"));
+
+        let report = redact(&args("no such text")).unwrap();
+        assert_eq!(report.code, 1);
+        assert_eq!(report.warnings.len(), 1);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_launch_options() {
+        let args: Vec<String> = ["tui", "report.pdf", "--page", "37", "--goto", "20:14"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let launch = LaunchOptions::parse(&args).unwrap();
+        assert_eq!(launch.file, Some(PathBuf::from("report.pdf")));
+        assert_eq!(launch.page, Some(36));
+        assert_eq!(launch.goto, Some((19, 13)));
+
+        assert!(parse_cell("0:4").is_err());
+        assert!(parse_cell("12").is_err());
+        assert!(LaunchOptions::parse(&args[..0]).unwrap().file.is_none());
+
+        assert_eq!(parse_cell_size("10x20").unwrap(), (10, 20));
+        assert!(parse_cell_size("10").is_err());
+        assert!(parse_cell_size("0x20").is_err());
+    }
+}
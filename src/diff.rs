@@ -0,0 +1,176 @@
+use crate::export::ExtractedDocument;
+use serde::Serialize;
+use similar::{ChangeTag, TextDiff};
+use std::collections::BTreeMap;
+
+// ============= EXTRACTION DIFF =============
+//
+// Pages are aligned by page number, then the matrix lines of each pair are
+// diffed. Each contiguous run of changed lines is reported as one region.
+
+#[derive(Serialize)]
+pub struct ChangedRegion {
+    /// 1-based first line in the old page and the number of lines removed
+    pub old_start: usize,
+    pub old_count: usize,
+    /// 1-based first line in the new page and the number of lines added
+    pub new_start: usize,
+    pub new_count: usize,
+    pub removed: Vec<String>,
+    pub added: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct PageDiff {
+    pub page: usize,
+    pub regions: Vec<ChangedRegion>,
+}
+
+#[derive(Serialize)]
+pub struct DocumentDiff {
+    pub old: String,
+    pub new: String,
+    pub pages_changed: usize,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+    pub pages: Vec<PageDiff>,
+}
+
+impl DocumentDiff {
+    pub fn compute(old: &ExtractedDocument, new: &ExtractedDocument) -> Self {
+        let mut aligned: BTreeMap<usize, (&[String], &[String])> = BTreeMap::new();
+        for page in &old.pages {
+            aligned.entry(page.page).or_default().0 = page.lines.as_slice();
+        }
+        for page in &new.pages {
+            aligned.entry(page.page).or_default().1 = page.lines.as_slice();
+        }
+
+        let pages: Vec<PageDiff> = aligned
+            .into_iter()
+            .map(|(page, (old_lines, new_lines))| PageDiff {
+                page,
+                regions: diff_lines(old_lines, new_lines),
+            })
+            .filter(|page| !page.regions.is_empty())
+            .collect();
+
+        let regions = pages.iter().flat_map(|p| &p.regions);
+        Self {
+            old: old.source.clone(),
+            new: new.source.clone(),
+            pages_changed: pages.len(),
+            lines_added: regions.clone().map(|r| r.added.len()).sum(),
+            lines_removed: regions.map(|r| r.removed.len()).sum(),
+            pages,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pages.is_empty()
+    }
+
+    /// Unified-diff style text with one hunk per changed region.
+    pub fn render_text(&self) -> String {
+        let mut out = format!("--- {}\n+++ {}\n", self.old, self.new);
+        for page in &self.pages {
+            for region in &page.regions {
+                out.push_str(&format!(
+                    "@@ page {} -{},{} +{},{} @@\n",
+                    page.page,
+                    region.old_start,
+                    region.old_count,
+                    region.new_start,
+                    region.new_count
+                ));
+                for line in &region.removed {
+                    out.push_str(&format!("-{}\n", line));
+                }
+                for line in &region.added {
+                    out.push_str(&format!("+{}\n", line));
+                }
+            }
+        }
+        out.push_str(&format!(
+            "\n{} page(s) changed, {} region(s), +{} -{} lines\n",
+            self.pages_changed,
+            self.pages.iter().map(|p| p.regions.len()).sum::<usize>(),
+            self.lines_added,
+            self.lines_removed
+        ));
+        out
+    }
+}
+
+fn diff_lines(old: &[String], new: &[String]) -> Vec<ChangedRegion> {
+    let old: Vec<&str> = old.iter().map(String::as_str).collect();
+    let new: Vec<&str> = new.iter().map(String::as_str).collect();
+    let diff = TextDiff::from_slices(&old, &new);
+
+    let mut regions = Vec::new();
+    for group in diff.grouped_ops(0) {
+        let (Some(first), Some(last)) = (group.first(), group.last()) else {
+            continue;
+        };
+        let mut region = ChangedRegion {
+            old_start: first.old_range().start + 1,
+            old_count: last.old_range().end - first.old_range().start,
+            new_start: first.new_range().start + 1,
+            new_count: last.new_range().end - first.new_range().start,
+            removed: Vec::new(),
+            added: Vec::new(),
+        };
+        for op in &group {
+            for change in diff.iter_changes(op) {
+                match change.tag() {
+                    ChangeTag::Delete => region.removed.push(change.value().to_string()),
+                    ChangeTag::Insert => region.added.push(change.value().to_string()),
+                    ChangeTag::Equal => {}
+                }
+            }
+        }
+        if !region.removed.is_empty() || !region.added.is_empty() {
+            regions.push(region);
+        }
+    }
+    regions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::ExtractedPage;
+
+    fn doc(pages: &[&[&str]]) -> ExtractedDocument {
+        ExtractedDocument {
+            source: "test".to_string(),
+            pages: pages
+                .iter()
+                .enumerate()
+                .map(|(i, lines)| ExtractedPage {
+                    page: i + 1,
+                    width: 10,
+                    height: lines.len(),
+                    lines: lines.iter().map(|l| l.to_string()).collect(),
+                    regions: Vec::new(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_page_aligned_diff() {
+        let old = doc(&[&["a", "b", "c"], &["same"]]);
+        let new = doc(&[&["a", "B", "c"], &["same"], &["extra"]]);
+        let diff = DocumentDiff::compute(&old, &new);
+
+        assert_eq!(diff.pages_changed, 2);
+        assert_eq!(diff.pages[0].page, 1);
+        assert_eq!(diff.pages[0].regions[0].old_start, 2);
+        assert_eq!(diff.pages[0].regions[0].removed, vec!["b"]);
+        assert_eq!(diff.pages[0].regions[0].added, vec!["B"]);
+        assert_eq!(diff.pages[1].page, 3);
+        assert_eq!((diff.lines_added, diff.lines_removed), (2, 1));
+        assert!(DocumentDiff::compute(&old, &old).is_empty());
+    }
+}
@@ -0,0 +1,518 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+// ============= EXPORT FORMATS =============
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    Text,
+    Markdown,
+    Json,
+    Csv,
+    Hocr,
+}
+
+impl ExportFormat {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "txt" | "text" => Ok(ExportFormat::Text),
+            "md" | "markdown" => Ok(ExportFormat::Markdown),
+            "json" => Ok(ExportFormat::Json),
+            "csv" => Ok(ExportFormat::Csv),
+            "hocr" => Ok(ExportFormat::Hocr),
+            other => Err(anyhow!(
+                "Unknown format '{}' (expected txt, md, json, csv or hocr)",
+                other
+            )),
+        }
+    }
+
+    /// Guess the format of a saved file from its extension.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let ext = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_string())
+            .ok_or_else(|| anyhow!("Can't tell the format of {}", path.display()))?;
+        Self::parse(&ext)
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Text => "txt",
+            ExportFormat::Markdown => "md",
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Hocr => "hocr",
+        }
+    }
+}
+
+// ============= PAGE RANGES =============
+
+/// Parse 1-based page ranges like `5`, `1-20` or `3,7-12` into 0-based page
+/// indices, in the order given and without repeats.
+pub fn parse_page_range(spec: &str, total_pages: usize) -> Result<Vec<usize>> {
+    let mut pages = Vec::new();
+    for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (start, end) = match part.split_once('-') {
+            Some((a, b)) => (a.trim().parse::<usize>()?, b.trim().parse::<usize>()?),
+            None => {
+                let page = part.parse::<usize>()?;
+                (page, page)
+            }
+        };
+
+        if start == 0 || start > end || end > total_pages {
+            return Err(anyhow!(
+                "Invalid page range '{}' (document has {} pages)",
+                part,
+                total_pages
+            ));
+        }
+        for page in start - 1..end {
+            if !pages.contains(&page) {
+                pages.push(page);
+            }
+        }
+    }
+    Ok(pages)
+}
+
+// ============= EXTRACTED DOCUMENT =============
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExtractedPage {
+    /// 1-based page number in the source PDF
+    pub page: usize,
+    pub width: usize,
+    pub height: usize,
+    /// Matrix rows with trailing spaces trimmed
+    pub lines: Vec<String>,
+    /// Layout blocks, when the extraction came from the GUI with its region edits
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub regions: Vec<ExtractedRegion>,
+}
+
+impl ExtractedPage {
+    /// Rebuild a page from saved lines, taking the widest line as the width.
+    pub fn from_lines(page: usize, lines: Vec<String>) -> Self {
+        Self {
+            page,
+            width: lines.iter().map(|l| l.chars().count()).max().unwrap_or(0),
+            height: lines.len(),
+            lines,
+            regions: Vec::new(),
+        }
+    }
+
+    pub fn from_matrix(page: usize, matrix: &[Vec<char>]) -> Self {
+        Self {
+            page,
+            width: matrix.first().map_or(0, |row| row.len()),
+            height: matrix.len(),
+            lines: matrix
+                .iter()
+                .map(|row| row.iter().collect::<String>().trim_end().to_string())
+                .collect(),
+            regions: Vec::new(),
+        }
+    }
+}
+
+/// A layout block on a page, in matrix cells.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExtractedRegion {
+    /// 1-based, as `R<id>` in the GUI
+    pub id: usize,
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+    pub block_type: String,
+    pub confidence: f32,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub table: Option<ExtractedTable>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExtractedTable {
+    pub cells: Vec<Vec<String>>,
+    pub header_rows: usize,
+}
+
+impl ExtractedTable {
+    pub fn columns(&self) -> usize {
+        self.cells.first().map_or(0, Vec::len)
+    }
+
+    /// A Markdown pipe table. Markdown has exactly one header row, so extra header
+    /// rows become body rows, and with none the header line is left blank.
+    pub fn to_markdown(&self) -> String {
+        let columns = self.columns();
+        if columns == 0 {
+            return String::new();
+        }
+        let line = |row: &[String]| {
+            let cells: Vec<String> = row.iter().map(|c| c.replace('|', "\\|")).collect();
+            format!("| {} |\n", cells.join(" | "))
+        };
+
+        let mut out = String::new();
+        let body = if self.header_rows > 0 {
+            out.push_str(&line(&self.cells[0]));
+            &self.cells[1..]
+        } else {
+            out.push_str(&line(&vec![String::new(); columns]));
+            &self.cells[..]
+        };
+        out.push_str(&format!("|{}\n", " --- |".repeat(columns)));
+        for row in body {
+            out.push_str(&line(row));
+        }
+        out
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExtractedDocument {
+    pub source: String,
+    pub pages: Vec<ExtractedPage>,
+}
+
+pub fn render(doc: &ExtractedDocument, format: ExportFormat) -> Result<String> {
+    let mut out = String::new();
+    match format {
+        ExportFormat::Text => {
+            for (i, page) in doc.pages.iter().enumerate() {
+                if i > 0 {
+                    out.push('\u{c}'); // Form feed between pages, like pdftotext
+                }
+                for line in &page.lines {
+                    out.push_str(line);
+                    out.push('\n');
+                }
+            }
+        }
+        ExportFormat::Markdown => {
+            out.push_str(&format!("# {}\n", doc.source));
+            for page in &doc.pages {
+                // Fenced block keeps the spatial layout intact
+                out.push_str(&format!("\n## Page {}\n\n```text\n", page.page));
+                for line in &page.lines {
+                    out.push_str(line);
+                    out.push('\n');
+                }
+                out.push_str("```\n");
+                if !page.regions.is_empty() {
+                    out.push_str("\n### Blocks\n\n");
+                }
+                for region in &page.regions {
+                    let text = region.text.split_whitespace().collect::<Vec<_>>().join(" ");
+                    out.push_str(&format!("- R{} {}: {}\n", region.id, region.block_type, text));
+                    if let Some(table) = &region.table {
+                        out.push('\n');
+                        out.push_str(&table.to_markdown());
+                        out.push('\n');
+                    }
+                }
+            }
+        }
+        ExportFormat::Json => {
+            out = serde_json::to_string_pretty(doc)?;
+            out.push('\n');
+        }
+        ExportFormat::Csv => {
+            out.push_str("page,line,text\n");
+            for page in &doc.pages {
+                for (idx, line) in page.lines.iter().enumerate() {
+                    if !line.trim().is_empty() {
+                        out.push_str(&format!("{},{},{}\n", page.page, idx + 1, csv_field(line)));
+                    }
+                }
+            }
+        }
+        ExportFormat::Hocr => render_hocr(doc, &mut out),
+    }
+    Ok(out)
+}
+
+// Grid cells in hOCR bboxes, matching the fixed character size Spatial lays out with
+const HOCR_CELL_WIDTH: usize = 6;
+const HOCR_CELL_HEIGHT: usize = 12;
+
+fn render_hocr(doc: &ExtractedDocument, out: &mut String) {
+    out.push_str(&format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \
+         \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">\n\
+         <html xmlns=\"http://www.w3.org/1999/xhtml\" xml:lang=\"en\" lang=\"en\">\n\
+         <head>\n<title>{}</title>\n\
+         <meta http-equiv=\"Content-Type\" content=\"text/html;charset=utf-8\" />\n\
+         <meta name=\"ocr-system\" content=\"chonker5-tui\" />\n\
+         <meta name=\"ocr-capabilities\" content=\"ocr_page ocr_line ocrx_word\" />\n\
+         </head>\n<body>\n",
+        html_escape(&doc.source)
+    ));
+
+    let bbox = |x0: usize, y0: usize, x1: usize, y1: usize| {
+        format!(
+            "bbox {} {} {} {}",
+            x0 * HOCR_CELL_WIDTH,
+            y0 * HOCR_CELL_HEIGHT,
+            x1 * HOCR_CELL_WIDTH,
+            y1 * HOCR_CELL_HEIGHT
+        )
+    };
+
+    for page in &doc.pages {
+        out.push_str(&format!(
+            "<div class=\"ocr_page\" id=\"page_{}\" title=\"{}; ppageno {}\">\n",
+            page.page,
+            bbox(0, 0, page.width, page.height),
+            page.page - 1
+        ));
+        for (row, line) in page.lines.iter().enumerate() {
+            let words = words_with_columns(line);
+            let (Some(first), Some(last)) = (words.first(), words.last()) else {
+                continue;
+            };
+            out.push_str(&format!(
+                "<span class=\"ocr_line\" id=\"line_{}_{}\" title=\"{}\">",
+                page.page,
+                row + 1,
+                bbox(first.0, row, last.0 + last.1.chars().count(), row + 1)
+            ));
+            for (i, (col, word)) in words.iter().enumerate() {
+                out.push_str(&format!(
+                    "{}<span class=\"ocrx_word\" id=\"word_{}_{}_{}\" title=\"{}\">{}</span>",
+                    if i > 0 { " " } else { "" },
+                    page.page,
+                    row + 1,
+                    i + 1,
+                    bbox(*col, row, col + word.chars().count(), row + 1),
+                    html_escape(word)
+                ));
+            }
+            out.push_str("</span>\n");
+        }
+        out.push_str("</div>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+}
+
+/// Split a matrix line into words with their starting column.
+fn words_with_columns(line: &str) -> Vec<(usize, String)> {
+    let mut words = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+    for (col, ch) in line.chars().enumerate() {
+        if ch.is_whitespace() {
+            words.extend(current.take());
+        } else {
+            current
+                .get_or_insert_with(|| (col, String::new()))
+                .1
+                .push(ch);
+        }
+    }
+    words.extend(current);
+    words
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// ============= SAVED ARTIFACTS =============
+
+/// Rebuild a document from the output of an earlier `render`, so it can be
+/// converted without re-extracting the PDF. Plain text has no page numbers,
+/// so its pages are numbered from 1.
+pub fn parse(content: &str, format: ExportFormat, source: &str) -> Result<ExtractedDocument> {
+    let mut doc = ExtractedDocument {
+        source: source.to_string(),
+        pages: Vec::new(),
+    };
+
+    match format {
+        ExportFormat::Text => {
+            if !content.is_empty() {
+                for (i, page) in content.split('\u{c}').enumerate() {
+                    let lines = page.lines().map(str::to_string).collect();
+                    doc.pages.push(ExtractedPage::from_lines(i + 1, lines));
+                }
+            }
+        }
+        ExportFormat::Markdown => {
+            let mut current: Option<(usize, Vec<String>)> = None;
+            let mut in_block = false;
+            for line in content.lines() {
+                if in_block {
+                    if line == "```" {
+                        in_block = false;
+                    } else if let Some((_, lines)) = current.as_mut() {
+                        lines.push(line.to_string());
+                    }
+                } else if let Some(number) = line.strip_prefix("## Page ") {
+                    if let Some((page, lines)) = current.take() {
+                        doc.pages.push(ExtractedPage::from_lines(page, lines));
+                    }
+                    current = Some((number.trim().parse()?, Vec::new()));
+                } else if let Some(title) = line.strip_prefix("# ") {
+                    doc.source = title.to_string();
+                } else if line == "```text" {
+                    in_block = true;
+                }
+            }
+            if let Some((page, lines)) = current {
+                doc.pages.push(ExtractedPage::from_lines(page, lines));
+            }
+        }
+        ExportFormat::Json => doc = serde_json::from_str(content)?,
+        ExportFormat::Csv => {
+            let mut pages: Vec<(usize, Vec<String>)> = Vec::new();
+            for (i, row) in content.lines().enumerate().skip(1) {
+                let mut fields = row.splitn(3, ',');
+                let (Some(page), Some(line), Some(text)) =
+                    (fields.next(), fields.next(), fields.next())
+                else {
+                    return Err(anyhow!("CSV row {} has fewer than 3 columns", i + 1));
+                };
+                let (page, line): (usize, usize) = (page.parse()?, line.parse()?);
+                let Some(index) = line.checked_sub(1) else {
+                    return Err(anyhow!("CSV row {} has line number 0", i + 1));
+                };
+
+                if pages.last().is_none_or(|(p, _)| *p != page) {
+                    pages.push((page, Vec::new()));
+                }
+                let lines = &mut pages.last_mut().unwrap().1;
+                // Blank lines aren't written to CSV, so pad back up to this line
+                if lines.len() < line {
+                    lines.resize(line, String::new());
+                }
+                lines[index] = csv_unescape(text);
+            }
+            doc.pages = pages
+                .into_iter()
+                .map(|(page, lines)| ExtractedPage::from_lines(page, lines))
+                .collect();
+        }
+        ExportFormat::Hocr => return Err(anyhow!("hOCR can be written but not read back")),
+    }
+    Ok(doc)
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn csv_unescape(field: &str) -> String {
+    match field.strip_prefix('"').and_then(|f| f.strip_suffix('"')) {
+        Some(quoted) => quoted.replace("\"\"", "\""),
+        None => field.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_page_range() {
+        assert_eq!(parse_page_range("1-3", 10).unwrap(), vec![0, 1, 2]);
+        assert_eq!(parse_page_range("5", 10).unwrap(), vec![4]);
+        assert!(parse_page_range("0-2", 10).is_err());
+        assert!(parse_page_range("4-2", 10).is_err());
+        assert!(parse_page_range("8-12", 10).is_err());
+        assert!(parse_page_range("x", 10).is_err());
+        assert_eq!(parse_page_range("3,7-9,8", 10).unwrap(), vec![2, 6, 7, 8]);
+        assert_eq!(parse_page_range(" 5, 1-2,", 9).unwrap(), vec![4, 0, 1]);
+    }
+
+    #[test]
+    fn test_saved_formats_round_trip() {
+        let doc = ExtractedDocument {
+            source: "report.pdf".to_string(),
+            pages: vec![
+                ExtractedPage::from_lines(
+                    1,
+                    vec!["Name,  \"Q1\"".into(), "".into(), "  Total  42".into()],
+                ),
+                ExtractedPage::from_lines(2, vec!["end".into()]),
+            ],
+        };
+
+        for format in [
+            ExportFormat::Text,
+            ExportFormat::Markdown,
+            ExportFormat::Json,
+            ExportFormat::Csv,
+        ] {
+            let parsed = parse(&render(&doc, format).unwrap(), format, "report.pdf").unwrap();
+            assert_eq!(parsed.pages.len(), 2, "{:?}", format);
+            assert_eq!(parsed.pages[0].lines, doc.pages[0].lines, "{:?}", format);
+            assert_eq!(parsed.pages[1].page, 2, "{:?}", format);
+        }
+        assert!(parse("", ExportFormat::Hocr, "x").is_err());
+        assert!(parse("page,line,text\n1,0,x\n", ExportFormat::Csv, "x").is_err());
+    }
+
+    #[test]
+    fn test_regions_reach_markdown_and_json() {
+        let mut page = ExtractedPage::from_lines(1, vec!["Qty  Price".into(), "2    4.50".into()]);
+        page.regions.push(ExtractedRegion {
+            id: 1,
+            x: 0,
+            y: 0,
+            width: 10,
+            height: 2,
+            block_type: "Table".to_string(),
+            confidence: 0.5,
+            text: "Qty  Price\n2    4.50".to_string(),
+            table: Some(ExtractedTable {
+                cells: vec![vec!["Qty".into(), "Price".into()], vec!["2".into(), "4.50".into()]],
+                header_rows: 1,
+            }),
+        });
+        let doc = ExtractedDocument {
+            source: "a.pdf".to_string(),
+            pages: vec![page],
+        };
+
+        let markdown = render(&doc, ExportFormat::Markdown).unwrap();
+        assert!(markdown.contains("- R1 Table: Qty Price 2 4.50\n"));
+        assert!(markdown.contains("| Qty | Price |\n| --- | --- |\n| 2 | 4.50 |\n"));
+        // The block list sits outside the fence, so the lines still read back
+        let parsed = parse(&markdown, ExportFormat::Markdown, "a.pdf").unwrap();
+        assert_eq!(parsed.pages[0].lines, doc.pages[0].lines);
+
+        let json = render(&doc, ExportFormat::Json).unwrap();
+        let parsed = parse(&json, ExportFormat::Json, "a.pdf").unwrap();
+        assert_eq!(parsed.pages[0].regions[0].block_type, "Table");
+        assert_eq!(parsed.pages[0].regions[0].table.as_ref().unwrap().columns(), 2);
+    }
+
+    #[test]
+    fn test_hocr_word_boxes() {
+        assert_eq!(
+            words_with_columns("  Total  42"),
+            vec![(2, "Total".to_string()), (9, "42".to_string())]
+        );
+        let doc = ExtractedDocument {
+            source: "a&b".to_string(),
+            pages: vec![ExtractedPage::from_lines(1, vec!["  Total  42".into()])],
+        };
+        let hocr = render(&doc, ExportFormat::Hocr).unwrap();
+        assert!(hocr.contains("<title>a&amp;b</title>"));
+        assert!(hocr.contains("title=\"bbox 12 0 42 12\">Total</span>"));
+    }
+}
@@ -7,7 +7,7 @@ pub fn get_pdf_path() -> Option<PathBuf> {
     let args: Vec<String> = env::args().collect();
     if args.len() > 1 {
         let path = PathBuf::from(&args[1]);
-        if path.exists() && path.extension().map_or(false, |ext| ext == "pdf") {
+        if path.exists() && path.extension().is_some_and(|ext| ext == "pdf") {
             return Some(path);
         }
     }
@@ -25,7 +25,7 @@ pub fn get_pdf_path() -> Option<PathBuf> {
         if let Ok(entries) = fs::read_dir(expanded.as_ref()) {
             for entry in entries.flatten() {
                 let path = entry.path();
-                if path.extension().map_or(false, |ext| ext == "pdf") {
+                if path.extension().is_some_and(|ext| ext == "pdf") {
                     // Return the first PDF found
                     return Some(path);
                 }
@@ -43,7 +43,7 @@ pub fn list_pdfs_in_directory(dir: &str) -> Vec<PathBuf> {
     if let Ok(entries) = fs::read_dir(expanded.as_ref()) {
         for entry in entries.flatten() {
             let path = entry.path();
-            if path.extension().map_or(false, |ext| ext == "pdf") {
+            if path.extension().is_some_and(|ext| ext == "pdf") {
                 pdfs.push(path);
             }
         }
@@ -1,3 +1,7 @@
+mod cli;
+mod diff;
+mod export;
+mod recent;
 mod spatial;
 use anyhow::Result;
 use copypasta::{ClipboardContext, ClipboardProvider};
@@ -7,6 +11,7 @@ use pdfium_render::prelude::*;
 use ratatui::{prelude::*, widgets::*};
 use ratatui_image::picker::Picker;
 use ratatui_image::{protocol::StatefulProtocol, StatefulImage};
+use recent::RecentFiles;
 use rfd::FileDialog;
 use spatial::Spatial;
 use std::path::PathBuf;
@@ -246,6 +251,9 @@ struct ChonkerTUI {
     // Advanced caching
     cache_hits: usize,
     cache_misses: usize,
+
+    // Recent documents
+    recent: RecentFiles,
 }
 
 impl ChonkerTUI {
@@ -293,12 +301,23 @@ impl ChonkerTUI {
             search_input_active: false,
             cache_hits: 0,
             cache_misses: 0,
+            recent: RecentFiles::load(),
         }
     }
 
+    /// Replace the 8x18 default with the terminal font's real cell size, so
+    /// page images keep their aspect ratio.
+    fn set_cell_size(&mut self, cell_size: (u16, u16)) {
+        let mut picker = Picker::new(cell_size);
+        picker.guess_protocol();
+        self.image_picker = Some(picker);
+        self.image_protocol = None;
+    }
+
     fn open_pdf(&mut self, path: PathBuf) -> Result<()> {
         if path.exists() {
-            self.pdf_path = Some(path.clone());
+            // Remember where we were in the previous document
+            self.remember_position();
 
             // Initialize PDFium and extract page count + render first page
             let (total_pages, pdf_image) = {
@@ -343,11 +362,20 @@ impl ChonkerTUI {
                 }
             };
 
+            // Only a document that loaded replaces the current one
+            self.pdf_path = Some(path.clone());
             self.total_pages = total_pages;
-            self.current_page = 0;
+            // Reopen recent documents on the page they were left at
+            self.current_page = self
+                .recent
+                .get(&path)
+                .map(|r| r.last_page)
+                .filter(|&page| page < total_pages)
+                .unwrap_or(0);
             self.pdf_image = pdf_image;
             self.image_protocol = None; // Reset image protocol for new PDF
             self.render_current_page()?;
+            let _ = self.recent.touch(&path, self.current_page);
             self.status_message = format!(
                 "Loaded: {} ({} pages)",
                 path.file_name().unwrap_or_default().to_string_lossy(),
@@ -359,6 +387,37 @@ impl ChonkerTUI {
         Ok(())
     }
 
+    /// Jump to a 0-based page and optionally a matrix cell, for deep links
+    /// like `chonker5-tui report.pdf --page 37 --goto 20:14`.
+    fn go_to(&mut self, page: Option<usize>, cell: Option<(usize, usize)>) -> Result<()> {
+        if let Some(page) = page {
+            self.current_page = page.min(self.total_pages.saturating_sub(1));
+            self.render_current_page()?;
+            self.remember_position();
+        }
+        if let Some((row, col)) = cell {
+            self.extract_matrix()?;
+            if let Some(matrix) = &self.editable_matrix {
+                let row = row.min(matrix.len().saturating_sub(1));
+                let col = col.min(matrix[row].len().saturating_sub(1));
+                self.cursor = (row, col);
+                self.status_message = format!(
+                    "Page {}, cell {}:{}",
+                    self.current_page + 1,
+                    row + 1,
+                    col + 1
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn remember_position(&mut self) {
+        if let Some(path) = &self.pdf_path {
+            let _ = self.recent.touch(path, self.current_page);
+        }
+    }
+
     fn render_current_page(&mut self) -> Result<()> {
         // Skip image rendering if zoom is outside safe range to prevent crashes
         // Use higher threshold to avoid ratatui_image crashes
@@ -1353,6 +1412,14 @@ Layout Analysis:
                             "PDF dark mode disabled".to_string()
                         };
                     }
+                    KeyCode::Char(c @ '1'..='9') if self.pdf_path.is_none() => {
+                        // Open a recent document from the start screen
+                        let index = c as usize - '1' as usize;
+                        if let Some(entry) = self.recent.entries().get(index) {
+                            let path = entry.path.clone();
+                            self.open_pdf(path)?;
+                        }
+                    }
                     KeyCode::Char(c)
                         if self.text_view_mode == TextViewMode::RawMatrix
                             && !key.modifiers.contains(KeyModifiers::CONTROL) =>
@@ -1631,6 +1698,19 @@ Layout Analysis:
                 .style(Style::default().fg(colors.fg))
                 .scroll(self.pdf_scroll);
             paragraph.render(inner, buf);
+        } else if !self.recent.entries().is_empty() {
+            let mut start_text = String::from("No PDF loaded\n\nRecent documents:\n");
+            for (i, entry) in self.recent.entries().iter().take(9).enumerate() {
+                start_text.push_str(&format!(
+                    "  {}  {} (p.{})\n",
+                    i + 1,
+                    entry.path.display(),
+                    entry.last_page + 1
+                ));
+            }
+            start_text.push_str("\nPress 1-9 to reopen, Ctrl+O to open a PDF file");
+            let paragraph = Paragraph::new(start_text).style(Style::default().fg(colors.dim));
+            paragraph.render(inner, buf);
         } else {
             let paragraph = Paragraph::new("No PDF loaded\n\nPress 'o' to open a PDF file")
                 .style(Style::default().fg(colors.dim));
@@ -1922,6 +2002,33 @@ Layout Analysis:
 
 // ============= MAIN =============
 fn main() -> Result<()> {
+    // Headless subcommands bypass the terminal UI entirely
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|a| cli::is_subcommand(a)) {
+        std::process::exit(cli::run(&args)?);
+    }
+    // Parse and open the PDF before entering raw mode, so a bad argument,
+    // path or page prints normally instead of leaving the terminal raw
+    let launch = cli::LaunchOptions::parse(&args)?;
+    let mut app = ChonkerTUI::new();
+    if let Some(cell_size) = launch.cell_size {
+        app.set_cell_size(cell_size);
+    }
+    if let Some(path) = launch.file {
+        app.open_pdf(path)?;
+        if app.pdf_path.is_none() {
+            return Err(anyhow::anyhow!("{}", app.status_message));
+        }
+        if let Some(page) = launch.page.filter(|&page| page >= app.total_pages) {
+            return Err(anyhow::anyhow!(
+                "--page {} is past the end of the document ({} pages)",
+                page + 1,
+                app.total_pages
+            ));
+        }
+        app.go_to(launch.page, launch.goto)?;
+    }
+
     // Terminal setup
     crossterm::terminal::enable_raw_mode()?;
     let mut stdout = std::io::stdout();
@@ -1935,22 +2042,24 @@ fn main() -> Result<()> {
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?;
 
-    // App state
-    let mut app = ChonkerTUI::new();
-
-    // Main loop
-    let mut should_quit = false;
-    while !should_quit {
-        // Draw
-        terminal.draw(|f| {
-            app.render(f.area(), f.buffer_mut());
-        })?;
-
-        // Handle events with short timeout for responsive UI
-        if event::poll(Duration::from_millis(50))? {
-            should_quit = app.handle_event(event::read()?)?;
+    // Main loop; errors fall through to the cleanup below before returning
+    let result = (|| -> Result<()> {
+        let mut should_quit = false;
+        while !should_quit {
+            // Draw
+            terminal.draw(|f| {
+                app.render(f.area(), f.buffer_mut());
+            })?;
+
+            // Handle events with short timeout for responsive UI
+            if event::poll(Duration::from_millis(50))? {
+                should_quit = app.handle_event(event::read()?)?;
+            }
         }
-    }
+        Ok(())
+    })();
+
+    app.remember_position();
 
     // Cleanup
     crossterm::terminal::disable_raw_mode()?;
@@ -1960,6 +2069,8 @@ fn main() -> Result<()> {
         crossterm::event::DisableMouseCapture
     )?;
 
+    result?;
+
     // Print summary
     if app.matrix_modified {
         println!("\nMatrix was modified but not saved.");
@@ -0,0 +1,169 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+// ============= RECENT DOCUMENTS =============
+//
+// Shared most-recently-used list. Stored as plain tab-separated lines so the
+// egui app (a standalone rust-script) can read and write the same file:
+//
+//     <last opened, unix seconds>\t<last page, 0-based>\t<path>
+
+const MAX_RECENT: usize = 10;
+
+#[derive(Clone, Debug)]
+pub struct RecentDocument {
+    pub path: PathBuf,
+    pub last_page: usize,
+    pub last_opened: i64,
+}
+
+pub struct RecentFiles {
+    entries: Vec<RecentDocument>,
+    store_path: Option<PathBuf>,
+}
+
+impl RecentFiles {
+    /// Load the list from `~/.chonker/recent`, starting empty if it is missing.
+    pub fn load() -> Self {
+        Self::load_from(
+            std::env::var_os("HOME")
+                .map(|home| PathBuf::from(home).join(".chonker").join("recent")),
+        )
+    }
+
+    /// Load the list from a specific file; unreadable lines are skipped.
+    fn load_from(store_path: Option<PathBuf>) -> Self {
+        let entries = store_path
+            .as_ref()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .map(|content| Self::parse(&content))
+            .unwrap_or_default();
+
+        Self {
+            entries,
+            store_path,
+        }
+    }
+
+    fn parse(content: &str) -> Vec<RecentDocument> {
+        content
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, '\t');
+                let last_opened = fields.next()?.parse().ok()?;
+                let last_page = fields.next()?.parse().ok()?;
+                let path = PathBuf::from(fields.next()?);
+                Some(RecentDocument {
+                    path,
+                    last_page,
+                    last_opened,
+                })
+            })
+            .take(MAX_RECENT)
+            .collect()
+    }
+
+    pub fn entries(&self) -> &[RecentDocument] {
+        &self.entries
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&RecentDocument> {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        self.entries.iter().find(|e| e.path == path)
+    }
+
+    /// Move `path` to the front of the list, recording the page the user is on.
+    pub fn touch(&mut self, path: &Path, page: usize) -> Result<()> {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        self.entries.retain(|e| e.path != path);
+        self.entries.insert(
+            0,
+            RecentDocument {
+                path,
+                last_page: page,
+                last_opened: chrono::Local::now().timestamp(),
+            },
+        );
+        self.entries.truncate(MAX_RECENT);
+        self.save()
+    }
+
+    fn save(&self) -> Result<()> {
+        let Some(store_path) = &self.store_path else {
+            return Ok(());
+        };
+        if let Some(dir) = store_path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let mut content = String::new();
+        for entry in &self.entries {
+            content.push_str(&format!(
+                "{}\t{}\t{}\n",
+                entry.last_opened,
+                entry.last_page,
+                entry.path.display()
+            ));
+        }
+        std::fs::write(store_path, content)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("chonker-recent-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_parse_skips_corrupt_lines() {
+        let entries = RecentFiles::parse(
+            "1700000000\t3\t/docs/a report.pdf\n\
+             garbage\n\
+             notanumber\t1\t/docs/b.pdf\n\
+             1700000001\t0\t/docs/tab\there.pdf\n",
+        );
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].path, PathBuf::from("/docs/a report.pdf"));
+        assert_eq!(entries[0].last_page, 3);
+        // Only the first two tabs separate fields
+        assert_eq!(entries[1].path, PathBuf::from("/docs/tab\there.pdf"));
+    }
+
+    #[test]
+    fn test_missing_store_starts_empty() {
+        let store = temp_store("missing");
+        assert!(RecentFiles::load_from(Some(store)).entries().is_empty());
+        assert!(RecentFiles::load_from(None).entries().is_empty());
+    }
+
+    #[test]
+    fn test_touch_dedupes_truncates_and_persists() {
+        let store = temp_store("touch");
+        let mut recent = RecentFiles::load_from(Some(store.clone()));
+        for i in 0..MAX_RECENT + 3 {
+            recent.touch(Path::new(&format!("/nonexistent/{}.pdf", i)), i).unwrap();
+        }
+        recent.touch(Path::new("/nonexistent/5.pdf"), 42).unwrap();
+
+        let reloaded = RecentFiles::load_from(Some(store));
+        let entries = reloaded.entries();
+        assert_eq!(entries.len(), MAX_RECENT);
+        assert_eq!(entries[0].path, PathBuf::from("/nonexistent/5.pdf"));
+        assert_eq!(entries[0].last_page, 42);
+        assert_eq!(entries.iter().filter(|e| e.path.ends_with("5.pdf")).count(), 1);
+        assert_eq!(
+            reloaded.get(Path::new("/nonexistent/12.pdf")).map(|e| e.last_page),
+            Some(12)
+        );
+        // The oldest entries fell off the end
+        assert!(reloaded.get(Path::new("/nonexistent/0.pdf")).is_none());
+    }
+}
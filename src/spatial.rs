@@ -3,25 +3,51 @@ use pdfium_render::prelude::*;
 
 pub struct Spatial;
 
+/// Optional page transforms applied before text is laid onto the grid.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ExtractOptions {
+    /// Clockwise rotation in degrees: 0, 90, 180 or 270
+    pub rotate: u16,
+    /// Keep only text inside `[x0, y0, x1, y1]`, in PDF points (origin bottom-left)
+    pub crop: Option<[f32; 4]>,
+}
+
 impl Spatial {
     pub fn extract(doc: &PdfDocument, pg: usize, tw: usize, th: usize) -> Result<Vec<Vec<char>>> {
+        Self::extract_with(doc, pg, tw, th, &ExtractOptions::default())
+    }
+
+    pub fn extract_with(
+        doc: &PdfDocument,
+        pg: usize,
+        tw: usize,
+        th: usize,
+        opts: &ExtractOptions,
+    ) -> Result<Vec<Vec<char>>> {
         let page = doc.pages().get(pg as u16)?;
-        let ph = page.height().value;
+        let (pw, ph) = (page.width().value, page.height().value);
+        // Page height after rotation, for flipping to top-down coordinates
+        let rh = if opts.rotate % 180 == 90 { pw } else { ph };
         let txt = page.text()?;
 
         let mut segs = vec![];
         for seg in txt.segments().iter() {
             let b = seg.bounds();
             let t = seg.text();
-            if !t.trim().is_empty() {
-                segs.push((
-                    t,
-                    b.left().value,
-                    ph - b.top().value,
-                    b.right().value - b.left().value,
-                    b.top().value - b.bottom().value,
-                ));
+            if t.trim().is_empty() {
+                continue;
             }
+            let rect = [
+                b.left().value,
+                b.bottom().value,
+                b.right().value,
+                b.top().value,
+            ];
+            if !inside_crop(rect, opts.crop) {
+                continue;
+            }
+            let [l, bottom, r, top] = rotate_rect(rect, opts.rotate, pw, ph);
+            segs.push((t, l, rh - top, r - l, top - bottom));
         }
 
         if segs.is_empty() {
@@ -84,3 +110,52 @@ impl Spatial {
         Ok(grid)
     }
 }
+
+/// Whether a `[left, bottom, right, top]` rect's centre is inside `crop`, so a
+/// segment is kept or dropped whole. No crop keeps everything.
+fn inside_crop(rect: [f32; 4], crop: Option<[f32; 4]>) -> bool {
+    let Some([x0, y0, x1, y1]) = crop else {
+        return true;
+    };
+    let (cx, cy) = ((rect[0] + rect[2]) / 2.0, (rect[1] + rect[3]) / 2.0);
+    cx >= x0 && cx <= x1 && cy >= y0 && cy <= y1
+}
+
+/// Rotate a `[left, bottom, right, top]` rect on a `pw` x `ph` page clockwise.
+fn rotate_rect(rect: [f32; 4], rotate: u16, pw: f32, ph: f32) -> [f32; 4] {
+    let turn = |x: f32, y: f32| match rotate {
+        90 => (y, pw - x),
+        180 => (pw - x, ph - y),
+        270 => (ph - y, x),
+        _ => (x, y),
+    };
+    let (ax, ay) = turn(rect[0], rect[1]);
+    let (bx, by) = turn(rect[2], rect[3]);
+    [ax.min(bx), ay.min(by), ax.max(bx), ay.max(by)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotate_rect() {
+        // 100 x 200 pt page
+        let rect = [10.0, 20.0, 30.0, 40.0];
+        assert_eq!(rotate_rect(rect, 0, 100.0, 200.0), rect);
+        assert_eq!(rotate_rect(rect, 90, 100.0, 200.0), [20.0, 70.0, 40.0, 90.0]);
+        assert_eq!(rotate_rect(rect, 180, 100.0, 200.0), [70.0, 160.0, 90.0, 180.0]);
+        assert_eq!(rotate_rect(rect, 270, 100.0, 200.0), [160.0, 10.0, 180.0, 30.0]);
+    }
+
+    #[test]
+    fn test_inside_crop() {
+        let crop = Some([0.0, 0.0, 100.0, 100.0]);
+        assert!(inside_crop([10.0, 10.0, 20.0, 20.0], crop));
+        assert!(!inside_crop([110.0, 10.0, 120.0, 20.0], crop));
+        // Straddling the edge goes by the centre
+        assert!(inside_crop([90.0, 10.0, 108.0, 20.0], crop));
+        assert!(!inside_crop([95.0, 10.0, 115.0, 20.0], crop));
+        assert!(inside_crop([500.0, 500.0, 510.0, 510.0], None));
+    }
+}